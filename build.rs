@@ -0,0 +1,48 @@
+//! Generates the solution registry for the `all` binary from the day modules in `src/bin/<year>`.
+use std::{env, fs, path::Path};
+
+fn is_number(s: &str, len: usize) -> bool {
+    s.len() == len && s.chars().all(|c| c.is_ascii_digit())
+}
+
+fn main() {
+    let bin_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/bin");
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let mut days: Vec<(String, String, String)> = vec![];
+
+    for entry in fs::read_dir(&bin_dir).unwrap() {
+        let year_dir = entry.unwrap().path();
+        let Some(year) = year_dir.file_name().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if !year_dir.is_dir() || !is_number(year, 4) {
+            continue;
+        }
+
+        println!("cargo:rerun-if-changed={}", year_dir.display());
+
+        days.extend(fs::read_dir(&year_dir).unwrap().filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let stem = path.file_stem()?.to_str()?.to_string();
+            let is_day = path.extension()? == "rs" && is_number(&stem, 2);
+            is_day.then(|| (year.to_string(), stem, path.to_str().unwrap().to_string()))
+        }));
+    }
+    days.sort();
+
+    let mut modules = String::new();
+    let mut entries = String::new();
+
+    for (year, day, path) in &days {
+        modules.push_str(&format!("#[path = {path:?}]\nmod day_{year}_{day};\n"));
+        entries.push_str(&format!("    day_{year}_{day}::SOLUTION,\n"));
+    }
+
+    let registry = format!(
+        "{modules}\nstatic REGISTRY: advent_of_code::template::registry::Registry =\n    advent_of_code::template::registry::Registry::new(&[\n{entries}    ]);\n"
+    );
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("registry.rs");
+    fs::write(out, registry).unwrap();
+}
@@ -64,7 +64,7 @@ impl PartialOrd for CubeSet {
     }
 }
 
-fn parse_color(input: &str) -> IResult<CubeColor> {
+fn parse_color(input: &str) -> IResult<'_, CubeColor> {
     map_res(alpha0, |color| match color {
         "red" => Ok(CubeColor::Red),
         "green" => Ok(CubeColor::Green),
@@ -73,7 +73,7 @@ fn parse_color(input: &str) -> IResult<CubeColor> {
     })(input)
 }
 
-fn parse_cube_set(input: &str) -> IResult<CubeSet> {
+fn parse_cube_set(input: &str) -> IResult<'_, CubeSet> {
     let parse_color_count = separated_pair(number_parser::<u32>, tag(" "), parse_color);
     let (rest, color_counts) = separated_list0(tag(", "), parse_color_count)(input)?;
 
@@ -0,0 +1,138 @@
+use advent_of_code::intervals::{Interval, IntervalMap, IntervalSet};
+use advent_of_code::parsers::space_separated_numbers_parser;
+use itertools::Itertools;
+
+advent_of_code::solution!(5);
+
+fn parse_map(input: &str) -> IntervalMap<u64> {
+    input
+        .lines()
+        .skip(1)
+        .map(space_separated_numbers_parser::<u64>)
+        .map(Result::unwrap)
+        .map(|(_, numbers)| {
+            let dest_start = numbers[0];
+            let src_start = numbers[1];
+            let length = numbers[2];
+            (Interval::from_len(src_start, length), dest_start)
+        })
+        .collect()
+}
+
+fn solve(seeds: IntervalSet<u64>, maps: impl Iterator<Item = IntervalMap<u64>>) -> u64 {
+    let seed_to_location = maps.fold(IntervalMap::new(), |map, next| map.then(&next));
+    seed_to_location.map_set(&seeds).min().unwrap_or(u64::MAX)
+}
+
+fn parse_seeds(seeds_line: &str) -> Vec<u64> {
+    seeds_line
+        .split(": ")
+        .skip(1)
+        .map(space_separated_numbers_parser::<u64>)
+        .map(Result::unwrap)
+        .flat_map(|(_, numbers)| numbers)
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Option<u64> {
+    let mut chunks = input.split("\n\n");
+    let seeds = parse_seeds(chunks.next().unwrap())
+        .into_iter()
+        .map(|x| Interval::from_len(x, 1))
+        .collect();
+
+    let lowest_location = solve(seeds, chunks.map(parse_map));
+    Some(lowest_location)
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let mut chunks = input.split("\n\n");
+    let seeds = parse_seeds(chunks.next().unwrap())
+        .into_iter()
+        .tuples()
+        .map(|(start, length)| Interval::from_len(start, length))
+        .collect();
+
+    let lowest_location = solve(seeds, chunks.map(parse_map));
+    Some(lowest_location)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(35));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(46));
+    }
+
+    fn set(intervals: &[(u64, u64)]) -> IntervalSet<u64> {
+        intervals
+            .iter()
+            .map(|&(start, length)| Interval::from_len(start, length))
+            .collect()
+    }
+
+    fn map(entries: &[(u64, u64, u64)]) -> IntervalMap<u64> {
+        entries
+            .iter()
+            .map(|&(destination, source, length)| (Interval::from_len(source, length), destination))
+            .collect()
+    }
+
+    #[test]
+    fn step_example_two() {
+        // Yes, I checked all these manually. It was a pain.
+
+        let seeds = set(&[(79, 14), (55, 13)]);
+
+        let seed_to_soil = map(&[(50, 98, 2), (52, 50, 48)]);
+        let soils = seed_to_soil.map_set(&seeds);
+        assert_eq!(soils, set(&[(57, 13), (81, 14)]));
+
+        let soil_to_fertilizer = map(&[(0, 15, 37), (37, 52, 2), (39, 0, 15)]);
+        let fertilizers = soil_to_fertilizer.map_set(&soils);
+        assert_eq!(fertilizers, soils);
+
+        let fertilizer_to_water = map(&[(49, 53, 8), (0, 11, 42), (42, 0, 7), (57, 7, 4)]);
+        let waters = fertilizer_to_water.map_set(&fertilizers);
+        assert_eq!(waters, set(&[(53, 4), (61, 9), (81, 14)]));
+
+        let water_to_light = map(&[(88, 18, 7), (18, 25, 70)]);
+        let lights = water_to_light.map_set(&waters);
+        assert_eq!(lights, set(&[(46, 4), (54, 9), (74, 14)]));
+
+        let light_to_temperature = map(&[(45, 77, 23), (81, 45, 19), (68, 64, 13)]);
+        let temperatures = light_to_temperature.map_set(&lights);
+        assert_eq!(temperatures, set(&[(45, 11), (78, 3), (82, 4), (90, 9)]));
+
+        let temperature_to_humidity = map(&[(0, 69, 1), (1, 0, 69)]);
+        let humidities = temperature_to_humidity.map_set(&temperatures);
+        assert_eq!(humidities, set(&[(46, 11), (78, 3), (82, 4), (90, 9)]));
+
+        let humidity_to_location = map(&[(60, 56, 37), (56, 93, 4)]);
+        let locations = humidity_to_location.map_set(&humidities);
+        assert_eq!(locations, set(&[(46, 15), (82, 3), (86, 4), (94, 5)]));
+
+        // composing the maps first gives the same locations.
+        let seed_to_location = [
+            seed_to_soil,
+            soil_to_fertilizer,
+            fertilizer_to_water,
+            water_to_light,
+            light_to_temperature,
+            temperature_to_humidity,
+            humidity_to_location,
+        ]
+        .iter()
+        .fold(IntervalMap::new(), |map, next| map.then(next));
+        assert_eq!(seed_to_location.map_set(&seeds), locations);
+    }
+}
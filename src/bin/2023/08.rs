@@ -0,0 +1,140 @@
+use advent_of_code::cycles::{first_common_hit, Hits};
+use advent_of_code::graph::{parse_edge_lines, Graph, Interner, NodeId};
+use advent_of_code::Solution;
+
+advent_of_code::solution!(8, Day08);
+
+struct Network {
+    /// `0` for left and `1` for right, the label of the edge to follow.
+    directions: Vec<usize>,
+    nodes: Interner,
+    graph: Graph<usize>,
+}
+
+impl Network {
+    /// Follows the instructions from `start`, the state is the current node and the next instruction.
+    fn hits(&self, start: NodeId, is_end: impl Fn(NodeId) -> bool) -> Hits {
+        Hits::find(
+            (start, 0),
+            |(node, i)| {
+                let next = self.graph.edges(node)[self.directions[i]].to;
+                (next, (i + 1) % self.directions.len())
+            },
+            |&(node, _)| is_end(node),
+        )
+    }
+}
+
+struct Day08;
+
+impl Solution for Day08 {
+    type Parsed = Network;
+    type Error = String;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        let (directions, nodes) = input
+            .split_once("\n\n")
+            .ok_or("expecting directions and nodes separated by an empty line")?;
+        let (nodes, graph) = parse_edge_lines(nodes).map_err(|e| e.to_string())?;
+
+        let directions = directions
+            .chars()
+            .map(|c| match c {
+                'L' => Ok(0),
+                'R' => Ok(1),
+                _ => Err(format!("invalid instruction {c:?}, expecting `L` or `R`")),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if directions.is_empty() {
+            return Err("expecting at least one instruction".into());
+        }
+
+        // targets that have no line of their own have no edges to follow.
+        if let Some(node) = graph.nodes().find(|&node| graph.edges(node).len() != 2) {
+            return Err(format!(
+                "node {} has {} outgoing edges, expecting a left and a right one",
+                nodes.name(node),
+                graph.edges(node).len()
+            ));
+        }
+
+        Ok(Network {
+            directions,
+            nodes,
+            graph,
+        })
+    }
+
+    fn part_one(network: &Self::Parsed) -> Option<Self::Answer> {
+        let start = network.nodes.get("AAA")?;
+        let end = network.nodes.get("ZZZ")?;
+        network.hits(start, |node| node == end).first()
+    }
+
+    fn part_two(network: &Self::Parsed) -> Option<Self::Answer> {
+        let is_end: Vec<bool> = network
+            .nodes
+            .iter()
+            .map(|(_, name)| name.ends_with('Z'))
+            .collect();
+
+        // the ghosts may reach their ends after different prefixes and several times per cycle.
+        let hits: Vec<Hits> = network
+            .nodes
+            .iter()
+            .filter(|(_, name)| name.ends_with('A'))
+            .map(|(start, _)| network.hits(start, |node| is_end[node as usize]))
+            .collect();
+        first_common_hit(&hits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_example(example: u8) -> Network {
+        Day08::parse(&advent_of_code::template::read_example(
+            "examples", DAY, example,
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_part_one() {
+        let result = Day08::part_one(&parse_example(1));
+        assert_eq!(result, Some(2));
+        let result = Day08::part_one(&parse_example(2));
+        assert_eq!(result, Some(6));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = Day08::part_two(&parse_example(3));
+        assert_eq!(result, Some(6));
+    }
+
+    #[test]
+    fn rejects_invalid_networks() {
+        let parse = |input: &str| Day08::parse(input).err();
+
+        assert!(parse("\n\nAAA = (AAA, AAA)").is_some());
+        assert!(parse("LR\r\n\nAAA = (AAA, AAA)").is_some());
+        assert!(parse("LX\n\nAAA = (AAA, AAA)").is_some());
+        assert!(parse("L\n\nAAA = (BBB, AAA)").is_some_and(|e| e.contains("node BBB")));
+        assert!(parse("L\n\nAAA = (AAA)").is_some());
+        assert!(parse("LR\n\nAAA = (AAA, AAA)").is_none());
+    }
+
+    #[test]
+    fn test_part_two_offset_cycles() {
+        // the first ghost ends at steps 2, 4, 6, …, the second at steps 1, 4, 7, …
+        let network = Day08::parse(
+            "L\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11C, 11C)\n11C = (11Z, 11Z)\n\
+             22A = (22Z, 22Z)\n22Z = (22B, 22B)\n22B = (22C, 22C)\n22C = (22Z, 22Z)",
+        )
+        .unwrap();
+        assert_eq!(Day08::part_two(&network), Some(4));
+    }
+}
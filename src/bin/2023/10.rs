@@ -1,51 +1,7 @@
-use std::ops::{Index, IndexMut};
-
-use itertools::Itertools;
-advent_of_code::solution!(10);
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-struct Position {
-    row: usize,
-    col: usize,
-}
-
-impl Position {
-    fn go(&mut self, direction: Direction) {
-        match direction {
-            Direction::North => {
-                self.row -= 1;
-            }
-            Direction::South => {
-                self.row += 1;
-            }
-            Direction::East => {
-                self.col += 1;
-            }
-            Direction::West => {
-                self.col -= 1;
-            }
-        }
-    }
-}
-
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
-
-impl Direction {
-    fn invert(&self) -> Self {
-        match self {
-            Direction::North => Direction::South,
-            Direction::South => Direction::North,
-            Direction::East => Direction::West,
-            Direction::West => Direction::East,
-        }
-    }
-}
+use advent_of_code::geometry::{Direction, Point};
+use advent_of_code::grid::Grid;
+use advent_of_code::Solution;
+advent_of_code::solution!(10, Day10);
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 struct Pipe {
@@ -148,87 +104,90 @@ impl Tile {
         }
     }
 
-    fn pipe(&self) -> Pipe {
+    fn pipe(&self) -> Option<Pipe> {
         match self {
-            Tile::Pipe(pipe) => *pipe,
-            _ => panic!("not a pipe"),
+            Tile::Pipe(pipe) => Some(*pipe),
+            _ => None,
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Grid {
-    tiles: Vec<Vec<Tile>>,
-    start: Position,
+struct Sketch {
+    tiles: Grid<Tile>,
+    start: Point,
     loop_length: usize,
 }
 
-impl Grid {
-    fn new(mut tiles: Vec<Vec<Tile>>) -> Self {
-        let (row, start_row) = tiles
-            .iter()
-            .find_position(|row| row.contains(&Tile::Start))
-            .unwrap();
-        let (col, _) = start_row
-            .iter()
-            .find_position(|tile| matches!(tile, Tile::Start))
-            .unwrap();
-
-        let start = Position { row, col };
-
-        let connects_north = tiles[row - 1][col].connects(Direction::South);
-        let connects_south = tiles[row + 1][col].connects(Direction::North);
-        let connects_east = tiles[row][col + 1].connects(Direction::West);
-        let connects_west = tiles[row][col - 1].connects(Direction::East);
-
-        let start_pipe = match (connects_north, connects_east, connects_south, connects_west) {
-            (true, false, true, false) => Tile::Pipe(Pipe {
+impl Sketch {
+    fn new(mut tiles: Grid<Tile>) -> Result<Self, String> {
+        let (row, col) = tiles
+            .position(|tile| *tile == Tile::Start)
+            .ok_or("expecting a start tile `S`")?;
+        let start = Point::from_grid(row, col);
+
+        // neighbours outside of the grid do not connect.
+        let connects = |direction: Direction| {
+            tiles
+                .step(start, direction)
+                .is_some_and(|next| tiles[next].connects(direction.invert()))
+        };
+
+        let start_pipe = match Direction::ALL.map(connects) {
+            [true, false, true, false] => Pipe {
                 from: Direction::North,
                 to: Direction::South,
                 part_of_loop: true,
-            }),
-            (false, true, false, true) => Tile::Pipe(Pipe {
+            },
+            [false, true, false, true] => Pipe {
                 from: Direction::East,
                 to: Direction::West,
                 part_of_loop: true,
-            }),
-            (true, true, false, false) => Tile::Pipe(Pipe {
+            },
+            [true, true, false, false] => Pipe {
                 from: Direction::North,
                 to: Direction::East,
                 part_of_loop: true,
-            }),
-            (true, false, false, true) => Tile::Pipe(Pipe {
+            },
+            [true, false, false, true] => Pipe {
                 from: Direction::North,
                 to: Direction::West,
                 part_of_loop: true,
-            }),
-            (false, false, true, true) => Tile::Pipe(Pipe {
+            },
+            [false, false, true, true] => Pipe {
                 from: Direction::South,
                 to: Direction::West,
                 part_of_loop: true,
-            }),
-            (false, true, true, false) => Tile::Pipe(Pipe {
+            },
+            [false, true, true, false] => Pipe {
                 from: Direction::South,
                 to: Direction::East,
                 part_of_loop: true,
-            }),
-            _ => unreachable!(),
+            },
+            _ => return Err("expecting exactly two pipes connected to the start".into()),
         };
 
-        tiles[row][col] = start_pipe;
+        tiles[start] = Tile::Pipe(start_pipe);
+
+        // pipes that point out of the grid or into the ground do not form a loop.
+        let follow = |tiles: &Grid<Tile>, position: Point, direction: Direction| {
+            tiles
+                .step(position, direction)
+                .and_then(|next| Some((next, tiles[next].pipe()?)))
+                .ok_or_else(|| format!("the loop is broken at {position}"))
+        };
 
         let mut loop_length = 1;
-        let mut current_position = start;
-        let mut current_tile = tiles[start];
-        let mut go_direction = current_tile.pipe().from;
-        current_position.go(current_tile.pipe().outgoing(go_direction));
-        go_direction = current_tile.pipe().outgoing(go_direction);
-        current_tile = tiles[current_position];
+        let mut go_direction = start_pipe.outgoing(start_pipe.from);
+        let (mut current_position, mut current_pipe) = follow(&tiles, start, go_direction)?;
 
         while current_position != start {
             go_direction = go_direction.invert();
 
-            let mut current_pipe = tiles[current_position].pipe();
+            if !current_pipe.connects(go_direction) {
+                return Err(format!("the loop is broken at {current_position}"));
+            }
+
             // fix orientation
             if current_pipe.from != go_direction {
                 current_pipe.swap();
@@ -237,95 +196,82 @@ impl Grid {
             tiles[current_position] = Tile::Pipe(current_pipe);
 
             loop_length += 1;
-            current_position.go(current_tile.pipe().outgoing(go_direction));
-            go_direction = current_tile.pipe().outgoing(go_direction);
-            current_tile = tiles[current_position];
+            go_direction = current_pipe.outgoing(go_direction);
+            (current_position, current_pipe) = follow(&tiles, current_position, go_direction)?;
         }
-        Self {
+
+        Ok(Self {
             tiles,
             start,
             loop_length,
-        }
+        })
     }
 }
 
-impl Index<Position> for Vec<Vec<Tile>> {
-    type Output = Tile;
-
-    fn index(&self, index: Position) -> &Self::Output {
-        &self[index.row][index.col]
+fn parse_tile(c: char) -> Tile {
+    match c {
+        'S' => Tile::Start,
+        '|' => Tile::Pipe(Pipe {
+            from: Direction::North,
+            to: Direction::South,
+            part_of_loop: false,
+        }),
+        '-' => Tile::Pipe(Pipe {
+            from: Direction::East,
+            to: Direction::West,
+            part_of_loop: false,
+        }),
+        'L' => Tile::Pipe(Pipe {
+            from: Direction::North,
+            to: Direction::East,
+            part_of_loop: false,
+        }),
+        'J' => Tile::Pipe(Pipe {
+            from: Direction::North,
+            to: Direction::West,
+            part_of_loop: false,
+        }),
+        '7' => Tile::Pipe(Pipe {
+            from: Direction::South,
+            to: Direction::West,
+            part_of_loop: false,
+        }),
+        'F' => Tile::Pipe(Pipe {
+            from: Direction::South,
+            to: Direction::East,
+            part_of_loop: false,
+        }),
+        _ => Tile::Ground,
     }
 }
 
-impl IndexMut<Position> for Vec<Vec<Tile>> {
-    fn index_mut(&mut self, index: Position) -> &mut Self::Output {
-        &mut self[index.row][index.col]
+struct Day10;
+
+impl Solution for Day10 {
+    type Parsed = Sketch;
+    type Error = String;
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        let tiles = Grid::parse(input, parse_tile).map_err(|e| e.to_string())?;
+        Sketch::new(tiles)
     }
-}
 
-fn parse_line(line: &str) -> Vec<Tile> {
-    let mut tiles = vec![Tile::Ground; line.len()];
-    line.chars().enumerate().for_each(|(i, c)| match c {
-        'S' => tiles[i] = Tile::Start,
-        '|' => {
-            tiles[i] = Tile::Pipe(Pipe {
-                from: Direction::North,
-                to: Direction::South,
-                part_of_loop: false,
-            })
-        }
-        '-' => {
-            tiles[i] = Tile::Pipe(Pipe {
-                from: Direction::East,
-                to: Direction::West,
-                part_of_loop: false,
-            })
-        }
-        'L' => {
-            tiles[i] = Tile::Pipe(Pipe {
-                from: Direction::North,
-                to: Direction::East,
-                part_of_loop: false,
-            })
-        }
-        'J' => {
-            tiles[i] = Tile::Pipe(Pipe {
-                from: Direction::North,
-                to: Direction::West,
-                part_of_loop: false,
-            })
-        }
-        '7' => {
-            tiles[i] = Tile::Pipe(Pipe {
-                from: Direction::South,
-                to: Direction::West,
-                part_of_loop: false,
-            })
-        }
-        'F' => {
-            tiles[i] = Tile::Pipe(Pipe {
-                from: Direction::South,
-                to: Direction::East,
-                part_of_loop: false,
-            })
-        }
-        _ => {}
-    });
-    tiles
-}
+    fn part_one(grid: &Self::Parsed) -> Option<Self::Answer> {
+        Some((grid.loop_length / 2) as u32)
+    }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let grid = Grid::new(input.lines().map(parse_line).collect_vec());
-    Some((grid.loop_length / 2) as u32)
+    fn part_two(grid: &Self::Parsed) -> Option<Self::Answer> {
+        Some(count_enclosed(grid))
+    }
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let grid = Grid::new(input.lines().map(parse_line).collect_vec());
+fn count_enclosed(grid: &Sketch) -> u32 {
     let mut nest = 0;
-    for row in grid.tiles {
+    for row in grid.tiles.rows() {
         let mut winding_number = 0;
         let mut bend_stack: Vec<Pipe> = Vec::new();
-        for tile in row {
+        for &tile in row {
             match tile {
                 Tile::Pipe(pipe) if pipe.part_of_loop => match pipe.get_type() {
                     PipeType::Bend => {
@@ -358,34 +304,51 @@ pub fn part_two(input: &str) -> Option<u32> {
             }
         }
     }
-    Some(nest)
+    nest
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn parse(input: &str) -> Sketch {
+        Day10::parse(input).unwrap()
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4));
+        let grid = parse(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(Day10::part_one(&grid), Some(4));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1));
+        let grid = parse(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(Day10::part_two(&grid), Some(1));
     }
 
     #[test]
     fn test_part_two_1() {
-        let result = part_two(&advent_of_code::template::read_example("examples", DAY, 1));
-        assert_eq!(result, Some(4));
+        let grid = parse(&advent_of_code::template::read_example("examples", DAY, 1));
+        assert_eq!(Day10::part_two(&grid), Some(4));
     }
 
     #[test]
     fn test_part_two_2() {
-        let result = part_two(&advent_of_code::template::read_example("examples", DAY, 2));
-        assert_eq!(result, Some(8));
+        let grid = parse(&advent_of_code::template::read_example("examples", DAY, 2));
+        assert_eq!(Day10::part_two(&grid), Some(8));
+    }
+
+    #[test]
+    fn test_start_at_edge() {
+        let grid = parse("..F7.\n.FJ|.\nSJ.L7\n|F--J\nLJ...\n");
+        assert_eq!(Day10::part_one(&grid), Some(8));
+    }
+
+    #[test]
+    fn rejects_broken_loops() {
+        assert!(Day10::parse("...\n.S.\n...\n").is_err());
+        assert!(Day10::parse("S7\n|J\n-.\n").is_err());
+        assert!(Day10::parse("F7.\n|S7\n").is_err());
     }
 }
@@ -0,0 +1,111 @@
+use std::convert::Infallible;
+
+use advent_of_code::geometry::Point;
+use advent_of_code::Solution;
+use itertools::Itertools;
+
+advent_of_code::solution!(11, Day11);
+
+type Output = u64;
+
+struct Image {
+    galaxies: Vec<Point>,
+    empty_rows: Vec<Output>,
+    col_count: Vec<usize>,
+}
+
+fn parse_input(input: &str) -> Image {
+    let mut empty_rows = Vec::new();
+    let mut col_count = vec![0; input.lines().next().unwrap().len()];
+    let mut galaxies = Vec::new();
+    input.lines().enumerate().for_each(|(row, line)| {
+        let mut row_empty = true;
+        line.char_indices().for_each(|(col, c)| {
+            if c == '#' {
+                col_count[col] += 1;
+                row_empty = false;
+                galaxies.push(Point::from_grid(row, col));
+            }
+        });
+        if row_empty {
+            empty_rows.push(row as Output);
+        }
+    });
+
+    Image {
+        galaxies,
+        empty_rows,
+        col_count,
+    }
+}
+
+fn expand(image: &Image, multiplier: Output) -> Vec<Point> {
+    let mut galaxies = image.galaxies.clone();
+    let growth = (multiplier - 1).max(1) as i64;
+
+    for galaxy in galaxies.iter_mut() {
+        galaxy.y += growth
+            * image
+                .empty_rows
+                .iter()
+                .filter(|row| **row < galaxy.y as Output)
+                .count() as i64;
+        galaxy.x += growth
+            * image.col_count[0..galaxy.x as usize]
+                .iter()
+                .filter(|count| **count == 0)
+                .count() as i64;
+    }
+
+    galaxies
+}
+
+fn solve(image: &Image, multiplier: Output) -> Output {
+    let galaxies = expand(image, multiplier);
+    galaxies
+        .iter()
+        .tuple_combinations()
+        .fold(0, |acc, (a, b)| acc + a.manhattan_distance(*b))
+}
+
+struct Day11;
+
+impl Solution for Day11 {
+    type Parsed = Image;
+    type Error = Infallible;
+    type Answer = Output;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+        Ok(parse_input(input))
+    }
+
+    fn part_one(image: &Self::Parsed) -> Option<Self::Answer> {
+        Some(solve(image, 2))
+    }
+
+    fn part_two(image: &Self::Parsed) -> Option<Self::Answer> {
+        Some(solve(image, 1_000_000))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_example() -> Image {
+        Day11::parse(&advent_of_code::template::read_file("examples", DAY)).unwrap()
+    }
+
+    #[test]
+    fn test_part_one() {
+        let result = Day11::part_one(&parse_example());
+        assert_eq!(result, Some(374));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let image = parse_example();
+        assert_eq!(solve(&image, 10), 1030);
+        assert_eq!(solve(&image, 100), 8410);
+    }
+}
@@ -0,0 +1,16 @@
+//! Runs every solution in `src/bin` in-process. Invoked by `cargo all` and `cargo check-answers`.
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+use advent_of_code::template::commands::{all, check_answers};
+
+fn main() {
+    let result = if std::env::args().any(|x| x == "--check-answers") {
+        check_answers::main(&REGISTRY)
+    } else {
+        all::main(&REGISTRY)
+    };
+
+    if let Err(e) = result {
+        e.exit();
+    }
+}
@@ -0,0 +1,417 @@
+//! Cycle detection for states that are advanced by a function, like the positions of a machine that follows
+//! repeating instructions. Every state sequence over a finite set of states ends up in a cycle, the functions of
+//! this module do not return for sequences that never repeat.
+
+/// The shape of a sequence `start, f(start), f(f(start)), …` that runs into a cycle.
+///
+/// ```
+/// # use advent_of_code::cycles::{brent, Cycle};
+/// // 0, 1, 2, 3, 4, 2, 3, 4, …
+/// let cycle = brent(0, |x| if x == 4 { 2 } else { x + 1 });
+/// assert_eq!(cycle, Cycle { prefix: 2, length: 3 });
+/// assert_eq!(cycle.reduce(1_000_000_000), 4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    /// The number of steps before the first state of the cycle.
+    pub prefix: usize,
+    /// The number of states in the cycle, at least 1.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Returns the step before `prefix + length` that reaches the same state as `step`, so the state after a
+    /// billion steps can be found without simulating all of them.
+    #[must_use]
+    pub fn reduce(&self, step: usize) -> usize {
+        if step < self.prefix {
+            step
+        } else {
+            self.prefix + (step - self.prefix) % self.length
+        }
+    }
+}
+
+/// Finds the cycle with Brent's algorithm, which calls `f` fewer times than [`floyd`].
+pub fn brent<S: Clone + Eq>(start: S, f: impl Fn(S) -> S) -> Cycle {
+    // find the length by teleporting the tortoise to the hare at powers of two.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = f(start.clone());
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(hare);
+        length += 1;
+    }
+
+    // with the hare `length` steps ahead, both meet at the first state of the cycle.
+    let mut tortoise = start.clone();
+    let mut hare = (0..length).fold(start, |state, _| f(state));
+    let mut prefix = 0;
+
+    while tortoise != hare {
+        tortoise = f(tortoise);
+        hare = f(hare);
+        prefix += 1;
+    }
+
+    Cycle { prefix, length }
+}
+
+/// Finds the cycle with Floyd's tortoise and hare algorithm.
+pub fn floyd<S: Clone + Eq>(start: S, f: impl Fn(S) -> S) -> Cycle {
+    let mut tortoise = f(start.clone());
+    let mut hare = f(f(start.clone()));
+
+    while tortoise != hare {
+        tortoise = f(tortoise);
+        hare = f(f(hare));
+    }
+
+    let mut tortoise = start;
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = f(tortoise);
+        hare = f(hare);
+        prefix += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = f(tortoise.clone());
+    while tortoise != hare {
+        hare = f(hare);
+        length += 1;
+    }
+
+    Cycle { prefix, length }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The steps at which a cycling sequence is in a goal state.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hits {
+    pub cycle: Cycle,
+    /// Goal steps before the cycle, in ascending order.
+    pub prefix_hits: Vec<usize>,
+    /// Goal steps during the first pass through the cycle, in ascending order.
+    /// They repeat every `cycle.length` steps.
+    pub cycle_hits: Vec<usize>,
+}
+
+impl Hits {
+    /// Detects the cycle of `start` with [`brent`] and records the steps at which `is_goal` accepts the state.
+    ///
+    /// ```
+    /// # use advent_of_code::cycles::Hits;
+    /// // 0, 1, 2, 3, 4, 2, 3, 4, …
+    /// let hits = Hits::find(0, |x| if x == 4 { 2 } else { x + 1 }, |&x| x % 2 == 0);
+    /// assert_eq!(hits.prefix_hits, [0]);
+    /// assert_eq!(hits.cycle_hits, [2, 4]);
+    /// assert!(hits.contains(7));
+    /// ```
+    pub fn find<S: Clone + Eq>(start: S, f: impl Fn(S) -> S, is_goal: impl Fn(&S) -> bool) -> Self {
+        let cycle = brent(start.clone(), &f);
+        let mut prefix_hits = vec![];
+        let mut cycle_hits = vec![];
+        let mut state = start;
+
+        for step in 0..cycle.prefix + cycle.length {
+            if is_goal(&state) {
+                if step < cycle.prefix {
+                    prefix_hits.push(step);
+                } else {
+                    cycle_hits.push(step);
+                }
+            }
+            state = f(state);
+        }
+
+        Self {
+            cycle,
+            prefix_hits,
+            cycle_hits,
+        }
+    }
+
+    /// Returns `true` if the state after `step` steps is a goal.
+    #[must_use]
+    pub fn contains(&self, step: usize) -> bool {
+        let hits = if step < self.cycle.prefix {
+            &self.prefix_hits
+        } else {
+            &self.cycle_hits
+        };
+        hits.binary_search(&self.cycle.reduce(step)).is_ok()
+    }
+
+    /// The first goal step.
+    #[must_use]
+    pub fn first(&self) -> Option<usize> {
+        self.prefix_hits
+            .first()
+            .or(self.cycle_hits.first())
+            .copied()
+    }
+}
+
+/// The steps `residue + k * modulus` that are at least `min`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Progression {
+    residue: i128,
+    modulus: i128,
+    min: i128,
+}
+
+impl Progression {
+    /// The first step, [`None`] if it does not fit into an [`i128`].
+    fn first(&self) -> Option<i128> {
+        if self.residue >= self.min {
+            return Some(self.residue);
+        }
+
+        let gap = self.min - self.residue;
+        let periods = gap / self.modulus + i128::from(gap % self.modulus != 0);
+        periods.checked_mul(self.modulus)?.checked_add(self.residue)
+    }
+
+    /// The steps in both progressions, found with the chinese remainder theorem for moduli that may share factors.
+    /// Returns `Ok(None)` if the progressions have no step in common.
+    fn combine(&self, other: &Self) -> Result<Option<Self>, Overflow> {
+        let (gcd, x, _) = extended_gcd(self.modulus, other.modulus);
+        let difference = other.residue - self.residue;
+        if difference % gcd != 0 {
+            return Ok(None);
+        }
+
+        let modulus = (self.modulus / gcd)
+            .checked_mul(other.modulus)
+            .ok_or(Overflow)?;
+        let factor = (difference / gcd)
+            .checked_mul(x)
+            .ok_or(Overflow)?
+            .rem_euclid(other.modulus / gcd);
+        let residue = factor
+            .checked_mul(self.modulus)
+            .and_then(|offset| offset.checked_add(self.residue))
+            .ok_or(Overflow)?
+            .rem_euclid(modulus);
+
+        Ok(Some(Self {
+            residue,
+            modulus,
+            min: self.min.max(other.min),
+        }))
+    }
+}
+
+/// The combined cycle length does not fit into an [`i128`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Overflow;
+
+/// Returns `(gcd, x, y)` with `a * x + b * y == gcd`.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (gcd, x, y) = extended_gcd(b, a % b);
+        (gcd, y, x - a / b * y)
+    }
+}
+
+/// Returns the first step at which all sequences are in a goal state, [`None`] if that never happens or if the step
+/// cannot be computed because it, or the combined cycle length, overflows.
+///
+/// The cycles may start at different steps, have different lengths and contain several goals each.
+/// Every combination of goals is tracked, so the work grows with the product of the goals per cycle.
+///
+/// ```
+/// # use advent_of_code::cycles::{first_common_hit, Hits};
+/// let a = Hits::find(0, |x| (x + 1) % 4, |&x| x == 3); // 3, 7, 11, …
+/// let b = Hits::find(0, |x| (x + 1) % 6, |&x| x == 1); // 1, 7, 13, …
+/// assert_eq!(first_common_hit(&[a, b]), Some(7));
+/// ```
+#[must_use]
+pub fn first_common_hit(hits: &[Hits]) -> Option<usize> {
+    let prefix = hits.iter().map(|hits| hits.cycle.prefix).max()?;
+
+    // before all sequences are cycling, the candidates are the goals of the sequence with the longest prefix.
+    let longest = hits.iter().find(|hits| hits.cycle.prefix == prefix)?;
+    if let Some(step) = longest
+        .prefix_hits
+        .iter()
+        .find(|&&step| hits.iter().all(|hits| hits.contains(step)))
+    {
+        return Some(*step);
+    }
+
+    // afterwards, every sequence hits its goals periodically, so combine the progressions of all sequences.
+    let mut combined = vec![Progression {
+        residue: 0,
+        modulus: 1,
+        min: i128::try_from(prefix).ok()?,
+    }];
+
+    for hits in hits {
+        let progressions = hits.cycle_hits.iter().map(|&step| Progression {
+            residue: step as i128,
+            modulus: hits.cycle.length as i128,
+            min: step as i128,
+        });
+
+        let mut next = vec![];
+        for progression in progressions {
+            for combined in &combined {
+                next.extend(combined.combine(&progression).ok()?);
+            }
+        }
+        next.sort_unstable_by_key(|p| (p.modulus, p.residue, p.min));
+        next.dedup();
+        combined = next;
+    }
+
+    // a first step beyond `i128` is beyond `usize` as well, another progression may still start earlier.
+    let step = combined.iter().filter_map(Progression::first).min()?;
+    usize::try_from(step).ok()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{brent, first_common_hit, floyd, Cycle, Hits, Overflow, Progression};
+
+    /// 0 → 1 → … → `prefix + length - 1` → `prefix` → …
+    fn sequence(prefix: usize, length: usize) -> impl Fn(usize) -> usize {
+        move |x| {
+            if x + 1 == prefix + length {
+                prefix
+            } else {
+                x + 1
+            }
+        }
+    }
+
+    #[test]
+    fn detects_cycles() {
+        for prefix in 0..10 {
+            for length in 1..10 {
+                let expected = Cycle { prefix, length };
+                assert_eq!(brent(0, sequence(prefix, length)), expected);
+                assert_eq!(floyd(0, sequence(prefix, length)), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn reduces_steps() {
+        let cycle = Cycle {
+            prefix: 3,
+            length: 4,
+        };
+        assert_eq!(cycle.reduce(2), 2);
+        assert_eq!(cycle.reduce(3), 3);
+        assert_eq!(cycle.reduce(7), 3);
+        assert_eq!(cycle.reduce(1_000_000_002), 6);
+    }
+
+    #[test]
+    fn finds_goals() {
+        let hits = Hits::find(0, sequence(3, 4), |&x| x == 1 || x == 4 || x == 6);
+
+        assert_eq!(hits.prefix_hits, [1]);
+        assert_eq!(hits.cycle_hits, [4, 6]);
+        assert_eq!(hits.first(), Some(1));
+        let steps: Vec<usize> = (0..15).filter(|&step| hits.contains(step)).collect();
+        assert_eq!(steps, [1, 4, 6, 8, 10, 12, 14]);
+    }
+
+    #[test]
+    fn combines_aligned_cycles() {
+        let hits: Vec<Hits> = [2, 3, 4]
+            .into_iter()
+            .map(|length| Hits::find(0, sequence(0, length), |&x| x == 0))
+            .collect();
+        assert_eq!(first_common_hit(&hits), Some(0));
+
+        let hits: Vec<Hits> = [2, 3, 4]
+            .into_iter()
+            .map(|length| Hits::find(1, sequence(0, length), |&x| x == 0))
+            .collect();
+        assert_eq!(first_common_hit(&hits), Some(11));
+    }
+
+    #[test]
+    fn combines_offset_cycles() {
+        // 5, 11, 17, … and 3, 7, 11, …
+        let a = Hits::find(0, sequence(2, 6), |&x| x == 5);
+        let b = Hits::find(0, sequence(3, 4), |&x| x == 3);
+        assert_eq!(first_common_hit(&[a.clone(), b.clone()]), Some(11));
+
+        // brute force for several goals per cycle and goals in the prefix.
+        let c = Hits::find(0, sequence(4, 9), |&x| x == 2 || x == 6 || x == 10);
+        let d = Hits::find(0, sequence(1, 6), |&x| x == 0 || x == 2 || x == 5);
+        let all = [a, b, c, d];
+        for count in 1..=all.len() {
+            let hits = &all[..count];
+            let expected = (0..10_000).find(|&step| hits.iter().all(|hits| hits.contains(step)));
+            assert_eq!(first_common_hit(hits), expected, "{count} sequences");
+        }
+    }
+
+    #[test]
+    fn detects_missing_common_hits() {
+        // even and odd steps never meet.
+        let a = Hits::find(0, sequence(0, 2), |&x| x == 0);
+        let b = Hits::find(0, sequence(0, 4), |&x| x == 1 || x == 3);
+        assert_eq!(first_common_hit(&[a, b]), None);
+
+        let none = Hits::find(0, sequence(0, 3), |_| false);
+        assert_eq!(none.first(), None);
+        assert_eq!(first_common_hit(&[none]), None);
+        assert_eq!(first_common_hit(&[]), None);
+    }
+
+    #[test]
+    fn detects_overflows() {
+        let huge = |residue: i128, modulus: i128| Progression {
+            residue,
+            modulus,
+            min: 0,
+        };
+        let prime = 170_141_183_460_469_231_731_687_303_715_884_105_727; // 2^127 - 1
+        assert_eq!(huge(0, prime).combine(&huge(1, 2)), Err(Overflow));
+        assert_eq!(huge(2, 4).combine(&huge(1, 2)), Ok(None));
+        assert_eq!(huge(1, prime).first(), Some(1));
+        assert_eq!(
+            Progression {
+                min: 3,
+                ..huge(1, prime)
+            }
+            .first(),
+            None
+        );
+
+        // the first common hit of coprime cycles is beyond `usize`.
+        let large = |length: u64| Hits {
+            cycle: Cycle {
+                prefix: 0,
+                length: usize::try_from(length).unwrap(),
+            },
+            prefix_hits: vec![],
+            cycle_hits: vec![usize::try_from(length - 1).unwrap()],
+        };
+        let hits = [
+            large(4_294_967_291),
+            large(4_294_967_279),
+            large(4_294_967_231),
+        ];
+        assert_eq!(first_common_hit(&hits), None);
+    }
+}
@@ -1,7 +1,10 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use crate::Year;
+
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
 /// # Display
@@ -57,6 +60,21 @@ impl PartialOrd<u8> for Day {
 
 /* -------------------------------------------------------------------------- */
 
+impl Serialize for Day {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Day {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let day = u8::deserialize(deserializer)?;
+        Self::new(day).ok_or_else(|| de::Error::custom(DayFromStrError))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 impl FromStr for Day {
     type Err = DayFromStrError;
 
@@ -80,20 +98,23 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
-pub fn all_days() -> AllDays {
-    AllDays::new()
+/// An iterator that yields every day of advent in `year`, from the 1st to the last.
+pub fn all_days(year: Year) -> AllDays {
+    AllDays::new(year)
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent in a year, from the 1st to the last.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    pub fn new(year: Year) -> Self {
+        Self {
+            current: 1,
+            last: year.day_count(),
+        }
     }
 }
 
@@ -101,10 +122,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and a year has at most 25 days.
         let day = Day(self.current);
         self.current += 1;
 
@@ -114,6 +135,117 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// A range of days, parsed from `N`, `N-M` or `N..` (from `N` to the last day).
+///
+/// ```
+/// # use advent_of_code::{Day, DayRange};
+/// let range: DayRange = "3-5".parse().unwrap();
+/// assert!(range.contains(Day::new(4).unwrap()));
+/// assert!(!range.contains(Day::new(6).unwrap()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayRange {
+    start: Day,
+    /// [`None`] if the range is open-ended.
+    end: Option<Day>,
+}
+
+impl DayRange {
+    /// Returns `true` if `day` is within the range (inclusive).
+    pub fn contains(&self, day: Day) -> bool {
+        day >= self.start && self.end.is_none_or(|end| day <= end)
+    }
+}
+
+impl Display for DayRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.end {
+            Some(end) if end == self.start => write!(f, "{}", self.start.0),
+            Some(end) => write!(f, "{}-{}", self.start.0, end.0),
+            None => write!(f, "{}..", self.start.0),
+        }
+    }
+}
+
+impl FromStr for DayRange {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |s: &str| s.trim().parse::<Day>().map_err(|_| DaySetFromStrError);
+
+        let (start, end) = if let Some(start) = s.strip_suffix("..") {
+            (parse(start)?, None)
+        } else if let Some((start, end)) = s.split_once('-') {
+            (parse(start)?, Some(parse(end)?))
+        } else {
+            let day = parse(s)?;
+            (day, Some(day))
+        };
+
+        if end.is_some_and(|end| end < start) {
+            return Err(DaySetFromStrError);
+        }
+
+        Ok(Self { start, end })
+    }
+}
+
+/// A set of days, parsed from a comma-separated list of [`DayRange`]s, e.g. `1-5,8,10..`.
+///
+/// ```
+/// # use advent_of_code::{Day, DaySet};
+/// let days: DaySet = "1-5,8,10..".parse().unwrap();
+/// assert!(days.contains(Day::new(8).unwrap()));
+/// assert!(days.contains(Day::new(25).unwrap()));
+/// assert!(!days.contains(Day::new(9).unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySet(Vec<DayRange>);
+
+impl DaySet {
+    /// Returns `true` if `day` is within any range of the set.
+    pub fn contains(&self, day: Day) -> bool {
+        self.0.iter().any(|range| range.contains(day))
+    }
+}
+
+impl Display for DaySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, range) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{range}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// An error which can be returned when parsing a [`DayRange`] or [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError;
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a comma-separated list of days and ranges, e.g. `1-5,8,10..`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
@@ -134,11 +266,12 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, DaySet};
+    use crate::Year;
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = all_days(Year::__new_unchecked(2023));
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -167,6 +300,36 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn parses_day_sets() {
+        let days: DaySet = "1-5,8,10..".parse().unwrap();
+        let selected: Vec<u8> = all_days(Year::__new_unchecked(2023))
+            .filter(|&day| days.contains(day))
+            .map(Day::into_inner)
+            .collect();
+
+        assert_eq!(
+            selected,
+            vec![1, 2, 3, 4, 5, 8, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25]
+        );
+        assert_eq!(days.to_string(), "1-5,8,10..");
+    }
+
+    #[test]
+    fn rejects_invalid_day_sets() {
+        assert!("".parse::<DaySet>().is_err());
+        assert!("5-3".parse::<DaySet>().is_err());
+        assert!("1,,2".parse::<DaySet>().is_err());
+        assert!("0-3".parse::<DaySet>().is_err());
+        assert!("26..".parse::<DaySet>().is_err());
+    }
+
+    #[test]
+    fn all_days_iterator_short_year() {
+        let iter = all_days(Year::__new_unchecked(2025));
+        assert_eq!(iter.last(), Some(Day(12)));
+    }
 }
 
 /* -------------------------------------------------------------------------- */
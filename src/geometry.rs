@@ -0,0 +1,393 @@
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position on a grid or in the plane.
+///
+/// `x` grows to the right and `y` grows downwards, like columns and rows of a puzzle input.
+/// Coordinates are signed, so positions next to the input can be represented.
+///
+/// ```
+/// # use advent_of_code::geometry::{Direction, Point};
+/// let point = Point::from_grid(2, 5);
+/// assert_eq!(point + Direction::North, Point::new(5, 1));
+/// assert_eq!(point.to_grid(), Some((2, 5)));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A displacement between two [`Point`]s.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    #[must_use]
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Converts a `(row, col)` grid index into a point.
+    ///
+    /// # Panics
+    /// Panics if an index does not fit into an [`i64`].
+    #[must_use]
+    pub fn from_grid(row: usize, col: usize) -> Self {
+        let coordinate = |i: usize| i64::try_from(i).expect("grid index fits into i64");
+        Self::new(coordinate(col), coordinate(row))
+    }
+
+    /// Converts the point into a `(row, col)` grid index, [`None`] if a coordinate is negative.
+    #[must_use]
+    pub fn to_grid(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.y).ok()?, usize::try_from(self.x).ok()?))
+    }
+
+    /// Returns the point one step into `direction`.
+    #[must_use]
+    pub fn step(self, direction: impl Into<Vector>) -> Self {
+        self + direction.into()
+    }
+
+    /// Returns the point one step into `direction`, [`None`] if it is outside of a grid of `width` columns and
+    /// `height` rows.
+    ///
+    /// ```
+    /// # use advent_of_code::geometry::{Direction, Point};
+    /// let corner = Point::ORIGIN;
+    /// assert_eq!(corner.checked_step(Direction::East, 3, 2), Some(Point::new(1, 0)));
+    /// assert_eq!(corner.checked_step(Direction::North, 3, 2), None);
+    /// ```
+    #[must_use]
+    pub fn checked_step(
+        self,
+        direction: impl Into<Vector>,
+        width: usize,
+        height: usize,
+    ) -> Option<Self> {
+        let next = self.step(direction);
+        next.to_grid()
+            .filter(|&(row, col)| row < height && col < width)
+            .map(|_| next)
+    }
+
+    /// The number of orthogonal steps between two points.
+    #[must_use]
+    pub fn manhattan_distance(self, other: Self) -> u64 {
+        (other - self).manhattan_length()
+    }
+
+    /// The number of steps between two points if diagonal steps are allowed.
+    #[must_use]
+    pub fn chebyshev_distance(self, other: Self) -> u64 {
+        (other - self).chebyshev_length()
+    }
+}
+
+impl Vector {
+    pub const ZERO: Self = Self::new(0, 0);
+
+    #[must_use]
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    #[must_use]
+    pub fn manhattan_length(self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    #[must_use]
+    pub fn chebyshev_length(self) -> u64 {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Display for Vector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<{}, {}>", self.x, self.y)
+    }
+}
+
+impl<V: Into<Vector>> Add<V> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: V) -> Self::Output {
+        let rhs = rhs.into();
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<V: Into<Vector>> AddAssign<V> for Point {
+    fn add_assign(&mut self, rhs: V) {
+        *self = *self + rhs;
+    }
+}
+
+impl<V: Into<Vector>> Sub<V> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: V) -> Self::Output {
+        self + -rhs.into()
+    }
+}
+
+impl<V: Into<Vector>> SubAssign<V> for Point {
+    fn sub_assign(&mut self, rhs: V) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Self::Output {
+        Vector::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Self::Output {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Vector::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the 4 orthogonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// All directions, clockwise starting from [`Direction::North`].
+    pub const ALL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    /// Turns by 90° clockwise.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Turns by 90° counter-clockwise.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    /// Returns the opposite direction.
+    #[must_use]
+    pub fn invert(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// The displacement of a single step, `North` decreases `y`.
+    #[must_use]
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction::North => Vector::new(0, -1),
+            Direction::East => Vector::new(1, 0),
+            Direction::South => Vector::new(0, 1),
+            Direction::West => Vector::new(-1, 0),
+        }
+    }
+
+    /// Parses `N`/`E`/`S`/`W`, `U`/`R`/`D`/`L` and arrows like `^`, as used by puzzle inputs.
+    #[must_use]
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'N' | 'U' | '^' => Some(Direction::North),
+            'E' | 'R' | '>' => Some(Direction::East),
+            'S' | 'D' | 'v' => Some(Direction::South),
+            'W' | 'L' | '<' => Some(Direction::West),
+            _ => None,
+        }
+    }
+}
+
+impl From<Direction> for Vector {
+    fn from(direction: Direction) -> Self {
+        direction.vector()
+    }
+}
+
+/// One of the 8 directions, including diagonals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// All directions, clockwise starting from [`Direction8::North`].
+    pub const ALL: [Self; 8] = [
+        Self::North,
+        Self::NorthEast,
+        Self::East,
+        Self::SouthEast,
+        Self::South,
+        Self::SouthWest,
+        Self::West,
+        Self::NorthWest,
+    ];
+
+    /// Turns by 45° clockwise.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turns by 45° counter-clockwise.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    /// Returns the opposite direction.
+    #[must_use]
+    pub fn invert(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// Returns `true` for the 4 diagonal directions.
+    #[must_use]
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// The displacement of a single step, a diagonal step moves along both axes.
+    #[must_use]
+    pub fn vector(self) -> Vector {
+        let (x, y) = match self {
+            Direction8::North => (0, -1),
+            Direction8::NorthEast => (1, -1),
+            Direction8::East => (1, 0),
+            Direction8::SouthEast => (1, 1),
+            Direction8::South => (0, 1),
+            Direction8::SouthWest => (-1, 1),
+            Direction8::West => (-1, 0),
+            Direction8::NorthWest => (-1, -1),
+        };
+        Vector::new(x, y)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        Self::ALL[direction as usize * 2]
+    }
+}
+
+impl From<Direction8> for Vector {
+    fn from(direction: Direction8) -> Self {
+        direction.vector()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction, Direction8, Point, Vector};
+
+    #[test]
+    fn does_vector_arithmetic() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -2);
+
+        assert_eq!(b - a, Vector::new(3, -4));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(a - Vector::new(1, 2), Point::ORIGIN);
+        assert_eq!(
+            Direction::East.vector() * 3 + Vector::new(0, 1),
+            Vector::new(3, 1)
+        );
+
+        let mut c = a;
+        c += Direction8::SouthEast;
+        c -= Direction::North;
+        assert_eq!(c, Point::new(2, 4));
+    }
+
+    #[test]
+    fn measures_distances() {
+        let a = Point::new(1, 6);
+        let b = Point::new(5, 11);
+        assert_eq!(a.manhattan_distance(b), 9);
+        assert_eq!(b.manhattan_distance(a), 9);
+        assert_eq!(a.chebyshev_distance(b), 5);
+    }
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::West.invert(), Direction::East);
+        assert!(Direction::ALL
+            .iter()
+            .all(|d| d.turn_left().turn_right() == *d && d.vector() == -d.invert().vector()));
+
+        assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+        assert_eq!(Direction8::NorthEast.invert(), Direction8::SouthWest);
+        assert_eq!(Direction8::from(Direction::South), Direction8::South);
+        assert_eq!(
+            Direction8::ALL.iter().filter(|d| d.is_diagonal()).count(),
+            4
+        );
+    }
+
+    #[test]
+    fn converts_grid_indices() {
+        assert_eq!(Point::from_grid(3, 7), Point::new(7, 3));
+        assert_eq!(Point::new(7, 3).to_grid(), Some((3, 7)));
+        assert_eq!(Point::ORIGIN.step(Direction::West).to_grid(), None);
+        assert_eq!(Point::new(2, 1).checked_step(Direction::South, 3, 2), None);
+        assert_eq!(Point::new(2, 1).checked_step(Direction::East, 3, 2), None);
+        assert_eq!(
+            Point::new(2, 1).checked_step(Direction8::NorthWest, 3, 2),
+            Some(Point::new(1, 0))
+        );
+        assert_eq!(Direction::from_char('^'), Some(Direction::North));
+        assert_eq!(Direction::from_char('x'), None);
+    }
+}
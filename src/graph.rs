@@ -0,0 +1,526 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::error::Error;
+use std::fmt::Display;
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, space0},
+    multi::separated_list1,
+    sequence::{delimited, separated_pair},
+    IResult,
+};
+
+/// A dense node id, handed out by an [`Interner`] or [`Graph::add_node`].
+pub type NodeId = u32;
+
+fn index(node: NodeId) -> usize {
+    node as usize
+}
+
+/// Maps node names to dense [`NodeId`]s, in the order the names are first seen.
+///
+/// ```
+/// # use advent_of_code::graph::Interner;
+/// let mut interner = Interner::new();
+/// assert_eq!(interner.intern("AAA"), 0);
+/// assert_eq!(interner.intern("11B"), 1);
+/// assert_eq!(interner.intern("AAA"), 0);
+/// assert_eq!(interner.name(1), "11B");
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Interner {
+    ids: HashMap<String, NodeId>,
+    names: Vec<String>,
+}
+
+impl Interner {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the id of `name`, assigning the next free id if the name is new.
+    ///
+    /// # Panics
+    /// Panics if there are more names than fit into a [`NodeId`].
+    pub fn intern(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = NodeId::try_from(self.names.len()).expect("node ids fit into u32");
+        self.ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+        id
+    }
+
+    /// Returns the id of `name` without interning it.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    /// Returns the name of an interned id.
+    ///
+    /// # Panics
+    /// Panics if the id was not handed out by this interner.
+    #[must_use]
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[index(id)]
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// All ids with their names, in the order they were interned.
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &str)> {
+        (0..).zip(self.names.iter().map(String::as_str))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A directed edge to `to`, carrying a `label` such as a cost or a direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Edge<L> {
+    pub to: NodeId,
+    pub label: L,
+}
+
+/// A directed graph stored as adjacency lists, edges of a node keep the order they were added in.
+///
+/// ```
+/// # use advent_of_code::graph::Graph;
+/// let mut graph = Graph::new();
+/// graph.add_edge(0, 1, 7);
+/// graph.add_edge(1, 2, 1);
+/// graph.add_edge(0, 2, 10);
+/// assert_eq!(graph.dijkstra(0, |&cost| cost), [Some(0), Some(7), Some(8)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph<L = ()> {
+    adjacency: Vec<Vec<Edge<L>>>,
+}
+
+impl<L> Default for Graph<L> {
+    fn default() -> Self {
+        Self { adjacency: vec![] }
+    }
+}
+
+impl<L> Graph<L> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a graph of `nodes` nodes without edges.
+    #[must_use]
+    pub fn with_nodes(nodes: usize) -> Self {
+        Self {
+            adjacency: std::iter::repeat_with(Vec::new).take(nodes).collect(),
+        }
+    }
+
+    /// Adds a node without edges and returns its id.
+    ///
+    /// # Panics
+    /// Panics if there are more nodes than fit into a [`NodeId`].
+    pub fn add_node(&mut self) -> NodeId {
+        self.adjacency.push(vec![]);
+        NodeId::try_from(self.adjacency.len() - 1).expect("node ids fit into u32")
+    }
+
+    /// Makes sure that `node` is part of the graph, adding nodes without edges up to it.
+    fn ensure_node(&mut self, node: NodeId) {
+        if index(node) >= self.adjacency.len() {
+            self.adjacency.resize_with(index(node) + 1, Vec::new);
+        }
+    }
+
+    /// Adds an edge from `from` to `to`, both nodes are added if they are not part of the graph yet.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, label: L) {
+        self.ensure_node(from.max(to));
+        self.adjacency[index(from)].push(Edge { to, label });
+    }
+
+    /// Adds an edge in both directions.
+    pub fn add_undirected_edge(&mut self, a: NodeId, b: NodeId, label: L)
+    where
+        L: Clone,
+    {
+        self.add_edge(a, b, label.clone());
+        self.add_edge(b, a, label);
+    }
+
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    #[must_use]
+    pub fn edge_count(&self) -> usize {
+        self.adjacency.iter().map(Vec::len).sum()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        (0..).take(self.adjacency.len())
+    }
+
+    /// The outgoing edges of `node`, empty for nodes that are not part of the graph.
+    #[must_use]
+    pub fn edges(&self, node: NodeId) -> &[Edge<L>] {
+        self.adjacency.get(index(node)).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn neighbours(&self, node: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges(node).iter().map(|edge| edge.to)
+    }
+
+    /// The number of edges between `start` and every node, [`None`] for unreachable nodes.
+    #[must_use]
+    pub fn bfs(&self, start: NodeId) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.node_count()];
+        let mut queue = VecDeque::new();
+
+        if let Some(distance) = distances.get_mut(index(start)) {
+            *distance = Some(0);
+            queue.push_back((start, 0));
+        }
+
+        while let Some((node, distance)) = queue.pop_front() {
+            for next in self.neighbours(node) {
+                if distances[index(next)].is_none() {
+                    distances[index(next)] = Some(distance + 1);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// The nodes reachable from `start` in depth-first pre-order, edges are followed in the order they were added.
+    #[must_use]
+    pub fn dfs(&self, start: NodeId) -> Vec<NodeId> {
+        let mut visited = vec![false; self.node_count()];
+        let mut order = vec![];
+        let mut stack = vec![start];
+
+        while let Some(node) = stack.pop() {
+            match visited.get_mut(index(node)) {
+                Some(seen) if !*seen => *seen = true,
+                _ => continue,
+            }
+            order.push(node);
+            // reversed, so that the first edge is popped first.
+            stack.extend(self.edges(node).iter().rev().map(|edge| edge.to));
+        }
+
+        order
+    }
+
+    /// The cheapest cost from `start` to every node, [`None`] for unreachable nodes.
+    /// `cost` returns the cost of an edge from its label.
+    #[must_use]
+    pub fn dijkstra(&self, start: NodeId, cost: impl Fn(&L) -> u64) -> Vec<Option<u64>> {
+        let mut costs = vec![None; self.node_count()];
+        let mut queue = BinaryHeap::new();
+        if index(start) < self.node_count() {
+            queue.push(Reverse((0, start)));
+        }
+
+        while let Some(Reverse((total, node))) = queue.pop() {
+            if costs[index(node)].is_some() {
+                continue;
+            }
+            costs[index(node)] = Some(total);
+
+            for edge in self.edges(node) {
+                if costs[index(edge.to)].is_none() {
+                    queue.push(Reverse((total + cost(&edge.label), edge.to)));
+                }
+            }
+        }
+
+        costs
+    }
+
+    /// The cheapest path from `start` to `goal` and its cost, [`None`] if `goal` is unreachable.
+    /// `heuristic` estimates the remaining cost from a node to `goal` and must never overestimate it.
+    #[must_use]
+    pub fn astar(
+        &self,
+        start: NodeId,
+        goal: NodeId,
+        cost: impl Fn(&L) -> u64,
+        heuristic: impl Fn(NodeId) -> u64,
+    ) -> Option<(u64, Vec<NodeId>)> {
+        let mut best: Vec<Option<u64>> = vec![None; self.node_count()];
+        let mut previous: Vec<Option<NodeId>> = vec![None; self.node_count()];
+        let mut queue = BinaryHeap::new();
+
+        *best.get_mut(index(start))? = Some(0);
+        queue.push(Reverse((heuristic(start), 0, start)));
+
+        while let Some(Reverse((_, total, node))) = queue.pop() {
+            if node == goal {
+                let mut path = vec![goal];
+                while let Some(node) = previous[index(*path.last()?)] {
+                    path.push(node);
+                }
+                path.reverse();
+                return Some((total, path));
+            }
+            if best[index(node)].is_some_and(|best| best < total) {
+                continue;
+            }
+
+            for edge in self.edges(node) {
+                let next_total = total + cost(&edge.label);
+                if best[index(edge.to)].is_none_or(|best| next_total < best) {
+                    best[index(edge.to)] = Some(next_total);
+                    previous[index(edge.to)] = Some(node);
+                    queue.push(Reverse((
+                        next_total + heuristic(edge.to),
+                        next_total,
+                        edge.to,
+                    )));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Orders the nodes so that every edge points from an earlier to a later node, [`None`] if the graph has a cycle.
+    /// Ties are broken by the smaller id.
+    #[must_use]
+    pub fn topological_sort(&self) -> Option<Vec<NodeId>> {
+        let mut incoming = vec![0usize; self.node_count()];
+        for edge in self.adjacency.iter().flatten() {
+            incoming[index(edge.to)] += 1;
+        }
+
+        let mut ready: BinaryHeap<Reverse<NodeId>> = self
+            .nodes()
+            .filter(|&node| incoming[index(node)] == 0)
+            .map(Reverse)
+            .collect();
+        let mut order = Vec::with_capacity(self.node_count());
+
+        while let Some(Reverse(node)) = ready.pop() {
+            order.push(node);
+            for next in self.neighbours(node) {
+                incoming[index(next)] -= 1;
+                if incoming[index(next)] == 0 {
+                    ready.push(Reverse(next));
+                }
+            }
+        }
+
+        (order.len() == self.node_count()).then_some(order)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses an edge line like `AAA = (BBB, CCC)` into the source and the targets.
+/// Names are alphanumeric, the parentheses are optional.
+pub fn edge_line_parser(input: &str) -> IResult<&str, (&str, Vec<&str>)> {
+    let targets = || separated_list1(tag(", "), alphanumeric1);
+
+    separated_pair(
+        alphanumeric1,
+        delimited(space0, tag("="), space0),
+        alt((delimited(tag("("), targets(), tag(")")), targets())),
+    )(input)
+}
+
+/// Parses lines like `AAA = (BBB, CCC)` into a graph with an edge from the source to each target.
+/// The label of an edge is the position of its target in the line, so `BBB` is at `0` and `CCC` at `1`.
+///
+/// # Errors
+/// Returns an error for the first line that is not an edge line.
+///
+/// ```
+/// # use advent_of_code::graph::parse_edge_lines;
+/// let (interner, graph) = parse_edge_lines("AAA = (BBB, CCC)\nBBB = (AAA, ZZZ)").unwrap();
+/// let bbb = interner.get("BBB").unwrap();
+/// assert_eq!(interner.name(graph.edges(bbb)[1].to), "ZZZ");
+/// ```
+pub fn parse_edge_lines(input: &str) -> Result<(Interner, Graph<usize>), ParseEdgesError> {
+    let mut interner = Interner::new();
+    let mut graph = Graph::new();
+
+    for (line_number, line) in input.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        if line.is_empty() {
+            continue;
+        }
+
+        let (source, targets) = match edge_line_parser(line) {
+            Ok(("", parsed)) => parsed,
+            _ => {
+                return Err(ParseEdgesError {
+                    line: line_number,
+                    content: line.to_string(),
+                })
+            }
+        };
+
+        let source = interner.intern(source);
+        graph.ensure_node(source);
+        for (position, target) in targets.into_iter().enumerate() {
+            let target = interner.intern(target);
+            graph.add_edge(source, target, position);
+        }
+    }
+
+    Ok((interner, graph))
+}
+
+/// An error which can be returned when parsing edge lines with [`parse_edge_lines`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEdgesError {
+    /// The zero-based number of the line that could not be parsed.
+    pub line: usize,
+    pub content: String,
+}
+
+impl Error for ParseEdgesError {}
+
+impl Display for ParseEdgesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {} is not an edge line like `AAA = (BBB, CCC)`: {:?}",
+            self.line, self.content
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{edge_line_parser, parse_edge_lines, Graph, Interner, ParseEdgesError};
+
+    /// 0 → 1 → 3, 0 → 2 → 3, 3 → 4, and 5 on its own.
+    fn diamond() -> Graph<u64> {
+        let mut graph = Graph::with_nodes(6);
+        graph.add_edge(0, 1, 1);
+        graph.add_edge(0, 2, 5);
+        graph.add_edge(1, 3, 10);
+        graph.add_edge(2, 3, 1);
+        graph.add_edge(3, 4, 2);
+        graph
+    }
+
+    #[test]
+    fn interns_names() {
+        let mut interner = Interner::new();
+        let ids: Vec<_> = ["AAA", "11A", "AAA", "ZZZ"]
+            .iter()
+            .map(|name| interner.intern(name))
+            .collect();
+
+        assert_eq!(ids, [0, 1, 0, 2]);
+        assert_eq!(interner.len(), 3);
+        assert_eq!(interner.get("ZZZ"), Some(2));
+        assert_eq!(interner.get("XXX"), None);
+        assert_eq!(
+            interner.iter().collect::<Vec<_>>(),
+            [(0, "AAA"), (1, "11A"), (2, "ZZZ")]
+        );
+    }
+
+    #[test]
+    fn builds_graphs() {
+        let mut graph = Graph::new();
+        let a = graph.add_node();
+        graph.add_undirected_edge(a, 3, ());
+
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph.neighbours(3).collect::<Vec<_>>(), [0]);
+        assert!(graph.edges(2).is_empty());
+        assert!(graph.edges(10).is_empty());
+    }
+
+    #[test]
+    fn searches_graphs() {
+        let graph = diamond();
+
+        assert_eq!(
+            graph.bfs(0),
+            [Some(0), Some(1), Some(1), Some(2), Some(3), None]
+        );
+        assert_eq!(graph.dfs(0), [0, 1, 3, 4, 2]);
+        assert_eq!(graph.dfs(5), [5]);
+        assert_eq!(
+            graph.dijkstra(0, |&cost| cost),
+            [Some(0), Some(1), Some(5), Some(6), Some(8), None]
+        );
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        let graph = diamond();
+
+        assert_eq!(
+            graph.astar(0, 4, |&cost| cost, |_| 0),
+            Some((8, vec![0, 2, 3, 4]))
+        );
+        assert_eq!(graph.astar(0, 0, |&cost| cost, |_| 0), Some((0, vec![0])));
+        assert_eq!(graph.astar(0, 5, |&cost| cost, |_| 0), None);
+        assert_eq!(
+            graph
+                .astar(0, 4, |_| 1, |node| u64::from(node != 4))
+                .map(|(cost, _)| cost),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn sorts_topologically() {
+        let mut graph = diamond();
+        assert_eq!(graph.topological_sort(), Some(vec![0, 1, 2, 3, 4, 5]));
+
+        graph.add_edge(4, 0, 0);
+        assert_eq!(graph.topological_sort(), None);
+    }
+
+    #[test]
+    fn parses_edge_lines() {
+        assert_eq!(
+            edge_line_parser("AAA = (BBB, CCC)"),
+            Ok(("", ("AAA", vec!["BBB", "CCC"])))
+        );
+        assert_eq!(edge_line_parser("a=b, c"), Ok(("", ("a", vec!["b", "c"]))));
+
+        let (interner, graph) = parse_edge_lines("11A = (11B, XXX)\n11B = (XXX, 11Z)\n").unwrap();
+        let start = interner.get("11A").unwrap();
+        let end = interner.get("11Z").unwrap();
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(graph.bfs(start)[end as usize], Some(2));
+        assert_eq!(graph.edges(start)[1].label, 1);
+
+        assert_eq!(
+            parse_edge_lines("AAA = (BBB, CCC)\nBBB -> CCC"),
+            Err(ParseEdgesError {
+                line: 1,
+                content: "BBB -> CCC".into()
+            })
+        );
+    }
+}
@@ -0,0 +1,471 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use crate::geometry::{Direction, Direction8, Point, Vector};
+
+/// A rectangular grid of cells, stored row by row in a single [`Vec`].
+///
+/// Cells are addressed by `(row, col)`, starting at the top left corner, or by a [`Point`].
+///
+/// ```
+/// # use advent_of_code::grid::Grid;
+/// let grid = Grid::parse("#.\n..", |c| c == '#').unwrap();
+/// assert_eq!(grid.width(), 2);
+/// assert_eq!(grid.get(0, 0), Some(&true));
+/// assert_eq!(grid.get(2, 0), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid of `width` columns from cells in row order.
+    ///
+    /// # Panics
+    /// Panics if the number of cells is not a multiple of `width`.
+    #[must_use]
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "{} cells do not fill rows of width {width}",
+            cells.len()
+        );
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a grid where every cell is `value`.
+    #[must_use]
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Parses a grid with one line per row, mapping each character to a cell.
+    /// Leading and trailing blank lines are ignored.
+    ///
+    /// # Errors
+    /// Returns an error if the rows differ in length.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, ParseGridError> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for (row, line) in input.trim_matches('\n').lines().enumerate() {
+            let line = line.trim_end_matches('\r');
+            let len = line.chars().count();
+
+            match width {
+                None => width = Some(len),
+                Some(width) if width != len => {
+                    return Err(ParseGridError {
+                        row,
+                        expected: width,
+                        found: len,
+                    })
+                }
+                Some(_) => {}
+            }
+
+            cells.extend(line.chars().map(&mut f));
+            height += 1;
+        }
+
+        Ok(Self {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `true` if `(row, col)` is inside the grid.
+    #[must_use]
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        row < self.height && col < self.width
+    }
+
+    /// Returns the index of `(row, col)` in [`Grid::cells`], if it is inside the grid.
+    #[must_use]
+    pub fn index_of(&self, row: usize, col: usize) -> Option<usize> {
+        self.contains(row, col).then_some(row * self.width + col)
+    }
+
+    /// Returns the `(row, col)` of an index in [`Grid::cells`].
+    #[must_use]
+    pub fn position_of(&self, index: usize) -> Option<(usize, usize)> {
+        (index < self.cells.len()).then(|| (index / self.width, index % self.width))
+    }
+
+    #[must_use]
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.index_of(row, col).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        self.index_of(row, col).map(|i| &mut self.cells[i])
+    }
+
+    /// All cells in row order.
+    #[must_use]
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Iterates over all cells with their `(row, col)`, in row order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i / width, i % width), cell))
+    }
+
+    /// Returns the `(row, col)` of the first cell, in row order, that matches `predicate`.
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .and_then(|i| self.position_of(i))
+    }
+
+    /// The cells of a row.
+    ///
+    /// # Panics
+    /// Panics if `row` is outside of the grid.
+    #[must_use]
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.height, "row {row} is outside of the grid");
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero width, a grid without columns has no rows either.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells of a column, from top to bottom.
+    ///
+    /// # Panics
+    /// Panics if `col` is outside of the grid.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {col} is outside of the grid");
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// The positions of the up to 4 orthogonally adjacent cells that are inside the grid.
+    pub fn neighbours4(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let point = Point::from_grid(row, col);
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction)?.to_grid())
+    }
+
+    /// The positions of the up to 8 adjacent cells, including diagonals, that are inside the grid.
+    /// Starts north of the cell and goes clockwise.
+    pub fn neighbours8(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let point = Point::from_grid(row, col);
+        Direction8::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction)?.to_grid())
+    }
+
+    /// Returns `true` if `point` is inside the grid.
+    #[must_use]
+    pub fn contains_point(&self, point: Point) -> bool {
+        point
+            .to_grid()
+            .is_some_and(|(row, col)| self.contains(row, col))
+    }
+
+    #[must_use]
+    pub fn get_at(&self, point: Point) -> Option<&T> {
+        let (row, col) = point.to_grid()?;
+        self.get(row, col)
+    }
+
+    pub fn get_at_mut(&mut self, point: Point) -> Option<&mut T> {
+        let (row, col) = point.to_grid()?;
+        self.get_mut(row, col)
+    }
+
+    /// Returns the point one step from `point` into `direction`, [`None`] if it is outside of the grid.
+    #[must_use]
+    pub fn step(&self, point: Point, direction: impl Into<Vector>) -> Option<Point> {
+        point.checked_step(direction, self.width, self.height)
+    }
+
+    /// Creates a grid of the same size with every cell mapped by `f`.
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, rows become columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |row, col| (col, row))
+    }
+
+    /// Rotates the grid by 90° clockwise.
+    #[must_use]
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |row, col| {
+            (self.height - 1 - col, row)
+        })
+    }
+
+    /// Rotates the grid by 90° counter-clockwise.
+    #[must_use]
+    pub fn rotate_counter_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |row, col| {
+            (col, self.width - 1 - row)
+        })
+    }
+
+    /// Builds a `width` × `height` grid, taking each cell from the `(row, col)` returned by `source`.
+    fn rebuild(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|(row, col)| {
+                let (row, col) = source(row, col);
+                self[(row, col)].clone()
+            })
+            .collect();
+
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        self.get(row, col)
+            .unwrap_or_else(|| panic!("({row}, {col}) is outside of the grid"))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get_at(point)
+            .unwrap_or_else(|| panic!("{point} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_at_mut(point)
+            .unwrap_or_else(|| panic!("{point} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        self.get_mut(row, col)
+            .unwrap_or_else(|| panic!("({row}, {col}) is outside of the grid"))
+    }
+}
+
+/// Renders one line per row, the cells of a row are not separated.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when parsing a [`Grid`] with rows of different lengths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseGridError {
+    /// The first row whose length differs from the first row.
+    pub row: usize,
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl Error for ParseGridError {}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "row {} has {} cells, expecting {} like the first row",
+            self.row, self.found, self.expected
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, ParseGridError};
+    use crate::geometry::{Direction, Direction8, Point};
+
+    fn sample() -> Grid<char> {
+        Grid::parse("abc\ndef\n", |c| c).unwrap()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = sample();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 'd');
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.position(|&c| c == 'e'), Some((1, 1)));
+
+        let grid = Grid::parse("12\r\n34", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(grid.cells(), &[1, 2, 3, 4]);
+
+        assert_eq!(Grid::parse("", |c| c).unwrap().height(), 0);
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        assert_eq!(
+            Grid::parse("ab\nabc", |c| c),
+            Err(ParseGridError {
+                row: 1,
+                expected: 2,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let grid = Grid::filled(3, 3, 0);
+
+        let corner: Vec<_> = grid.neighbours4(0, 0).collect();
+        assert_eq!(corner, vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours4(1, 1).count(), 4);
+
+        let corner: Vec<_> = grid.neighbours8(2, 2).collect();
+        assert_eq!(corner, vec![(1, 2), (2, 1), (1, 1)]);
+        assert_eq!(grid.neighbours8(1, 1).count(), 8);
+    }
+
+    #[test]
+    fn steps_inside_grid() {
+        let mut grid = sample();
+        let start = Point::from_grid(0, 2);
+
+        assert_eq!(grid[start], 'c');
+        assert_eq!(grid.step(start, Direction::East), None);
+        assert_eq!(grid.step(start, Direction::North), None);
+        assert_eq!(
+            grid.step(start, Direction8::SouthWest),
+            Some(Point::new(1, 1))
+        );
+        assert!(!grid.contains_point(Point::new(-1, 0)));
+
+        grid[Point::new(1, 1)] = 'x';
+        assert_eq!(grid.get_at(Point::new(1, 1)), Some(&'x'));
+    }
+
+    #[test]
+    fn views_rows_and_columns() {
+        let grid = sample();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+
+        let columns: Vec<String> = grid.columns().map(Iterator::collect).collect();
+        assert_eq!(columns, vec!["ad", "be", "cf"]);
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let grid = sample();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counter_clockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(
+            grid.rotate_clockwise().rotate_counter_clockwise(),
+            grid.clone()
+        );
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+
+    #[test]
+    fn updates_cells() {
+        let mut grid = Grid::from_vec(2, vec![0; 4]);
+        grid[(1, 1)] = 5;
+        *grid.get_mut(0, 1).unwrap() += 1;
+        assert_eq!(grid.get_mut(2, 0), None);
+        assert_eq!(grid.to_string(), "01\n05");
+        assert_eq!(
+            grid.iter()
+                .filter(|(_, &v)| v > 0)
+                .map(|(p, _)| p)
+                .collect::<Vec<_>>(),
+            vec![(0, 1), (1, 1)]
+        );
+    }
+}
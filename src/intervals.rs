@@ -0,0 +1,598 @@
+use std::ops::{Add, Range, Sub};
+
+/// A half-open interval `start..end` of ordered values.
+///
+/// An interval with `end <= start` is empty.
+///
+/// ```
+/// # use advent_of_code::intervals::Interval;
+/// let a = Interval::new(0, 10);
+/// let b = Interval::from_len(5, 15);
+/// assert_eq!(a.intersection(&b), Some(Interval::new(5, 10)));
+/// assert_eq!(b.difference(&a), (None, Some(Interval::new(10, 20))));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    #[must_use]
+    pub const fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    /// Creates the interval of `len` values starting at `start`.
+    #[must_use]
+    pub fn from_len(start: T, len: T) -> Self
+    where
+        T: Add<Output = T>,
+    {
+        Self::new(start, start + len)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// The number of values in the interval, zero if it is empty.
+    #[must_use]
+    pub fn len(&self) -> T
+    where
+        T: Sub<Output = T>,
+    {
+        self.end.max(self.start) - self.start
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: &T) -> bool {
+        self.start <= *value && *value < self.end
+    }
+
+    /// Returns `true` if both intervals contain a common value.
+    #[must_use]
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start.max(other.start) < self.end.min(other.end)
+    }
+
+    /// The values in both intervals, [`None`] if they do not overlap.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let intersection = Self::new(self.start.max(other.start), self.end.min(other.end));
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    /// The values of this interval that are not in `other`, split into the parts below and above `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> (Option<Self>, Option<Self>) {
+        if self.is_empty() {
+            return (None, None);
+        }
+        if other.is_empty() {
+            return (Some(*self), None);
+        }
+
+        let below = Self::new(self.start, self.end.min(other.start));
+        let above = Self::new(self.start.max(other.end), self.end);
+        (
+            (!below.is_empty()).then_some(below),
+            (!above.is_empty()).then_some(above),
+        )
+    }
+}
+
+impl<T> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Self {
+            start: range.start,
+            end: range.end,
+        }
+    }
+}
+
+impl<T> From<Interval<T>> for Range<T> {
+    fn from(interval: Interval<T>) -> Self {
+        interval.start..interval.end
+    }
+}
+
+/// Translates `value` from an interval starting at `from` to one starting at `to`.
+fn translate<T>(value: T, from: T, to: T) -> T
+where
+    T: Add<Output = T> + Sub<Output = T>,
+{
+    to + (value - from)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A set of values, stored as sorted intervals that neither overlap nor touch.
+///
+/// ```
+/// # use advent_of_code::intervals::{Interval, IntervalSet};
+/// let set: IntervalSet<u32> = [0..5, 3..8, 10..12].into_iter().collect();
+/// assert_eq!(set.intervals(), [Interval::new(0, 8), Interval::new(10, 12)]);
+///
+/// let other = IntervalSet::from(Interval::new(6, 11));
+/// assert_eq!(set.difference(&other).intervals(), [Interval::new(0, 6), Interval::new(11, 12)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self { intervals: vec![] }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sorts the intervals and merges the ones that overlap or touch, empty intervals are dropped.
+    fn normalize(mut intervals: Vec<Interval<T>>) -> Self {
+        intervals.retain(|interval| !interval.is_empty());
+        intervals.sort_unstable();
+
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+
+        Self { intervals: merged }
+    }
+
+    /// The intervals of the set, in ascending order.
+    #[must_use]
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The smallest value in the set.
+    #[must_use]
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    /// The end of the last interval, i.e. one past the largest value in the set.
+    #[must_use]
+    pub fn end(&self) -> Option<T> {
+        self.intervals.last().map(|interval| interval.end)
+    }
+
+    /// The number of values in the set.
+    #[must_use]
+    pub fn len(&self) -> T
+    where
+        T: Default + Add<Output = T> + Sub<Output = T>,
+    {
+        self.intervals
+            .iter()
+            .fold(T::default(), |len, interval| len + interval.len())
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: &T) -> bool {
+        let i = self
+            .intervals
+            .partition_point(|interval| interval.end <= *value);
+        self.intervals
+            .get(i)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    pub fn insert(&mut self, interval: impl Into<Interval<T>>) {
+        let mut intervals = std::mem::take(&mut self.intervals);
+        intervals.push(interval.into());
+        *self = Self::normalize(intervals);
+    }
+
+    /// The values in either set.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalize(self.iter().chain(other.iter()).copied().collect())
+    }
+
+    /// The values in both sets.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            intervals.extend(a.intersection(b));
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// The values in this set that are not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let mut others = other.intervals.iter().peekable();
+
+        for interval in &self.intervals {
+            let mut rest = Some(*interval);
+
+            // intervals of `other` that end before the current one can not overlap the following ones either.
+            while others
+                .next_if(|other| other.end <= interval.start)
+                .is_some()
+            {}
+
+            for other in others.clone() {
+                let Some(current) = rest else { break };
+                if other.start >= current.end {
+                    break;
+                }
+                let (below, above) = current.difference(other);
+                intervals.extend(below);
+                rest = above;
+            }
+
+            intervals.extend(rest);
+        }
+
+        Self { intervals }
+    }
+}
+
+impl<T: Copy + Ord> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self::normalize(vec![interval])
+    }
+}
+
+impl<T: Copy + Ord, I: Into<Interval<T>>> FromIterator<I> for IntervalSet<T> {
+    fn from_iter<It: IntoIterator<Item = I>>(iter: It) -> Self {
+        Self::normalize(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Interval<T>;
+    type IntoIter = std::slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A piecewise mapping of values: each source interval is shifted to start at a destination value,
+/// values outside of all source intervals map to themselves.
+///
+/// ```
+/// # use advent_of_code::intervals::{Interval, IntervalMap, IntervalSet};
+/// let mut map = IntervalMap::new();
+/// map.insert(Interval::from_len(98, 2), 50);
+/// map.insert(Interval::from_len(50, 48), 52);
+/// assert_eq!(map.get(99), 51);
+/// assert_eq!(map.get(10), 10);
+///
+/// let seeds = IntervalSet::from(Interval::from_len(95, 10));
+/// let soils = map.map_set(&seeds);
+/// assert_eq!(soils.intervals(), [Interval::new(50, 52), Interval::new(97, 105)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalMap<T> {
+    /// Source intervals with the destination of their start, sorted by source and not overlapping.
+    entries: Vec<(Interval<T>, T)>,
+}
+
+impl<T> Default for IntervalMap<T> {
+    fn default() -> Self {
+        Self { entries: vec![] }
+    }
+}
+
+impl<T> IntervalMap<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The source intervals and the destinations of their starts, sorted by source.
+    pub fn entries(&self) -> impl Iterator<Item = (Interval<T>, T)> + '_ {
+        self.entries.iter().copied()
+    }
+
+    /// The values that are covered by an entry, all other values map to themselves.
+    #[must_use]
+    pub fn sources(&self) -> IntervalSet<T> {
+        self.entries.iter().map(|(source, _)| *source).collect()
+    }
+
+    /// Maps `source` to the interval of the same length starting at `destination`.
+    /// Values of `source` that are already mapped by an earlier entry keep their mapping.
+    pub fn insert(&mut self, source: Interval<T>, destination: T) {
+        let unmapped = IntervalSet::from(source).difference(&self.sources());
+
+        for part in unmapped.iter() {
+            let i = self
+                .entries
+                .partition_point(|(entry, _)| entry.start < part.start);
+            let start = translate(part.start, source.start, destination);
+            self.entries.insert(i, (*part, start));
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, value: T) -> T {
+        let i = self
+            .entries
+            .partition_point(|(source, _)| source.end <= value);
+        match self.entries.get(i) {
+            Some((source, destination)) if source.contains(&value) => {
+                translate(value, source.start, *destination)
+            }
+            _ => value,
+        }
+    }
+
+    /// Splits `interval` at the boundaries of the entries, returning each part with the interval it maps to.
+    fn split(&self, interval: Interval<T>) -> Vec<(Interval<T>, Interval<T>)> {
+        let mut parts = vec![];
+        if interval.is_empty() {
+            return parts;
+        }
+
+        let first = self
+            .entries
+            .partition_point(|(source, _)| source.end <= interval.start);
+        let mut cursor = interval.start;
+
+        for (source, destination) in &self.entries[first..] {
+            let Some(part) = source.intersection(&interval) else {
+                break;
+            };
+
+            if cursor < part.start {
+                let identity = Interval::new(cursor, part.start);
+                parts.push((identity, identity));
+            }
+
+            let mapped = Interval::new(
+                translate(part.start, source.start, *destination),
+                translate(part.end, source.start, *destination),
+            );
+            parts.push((part, mapped));
+            cursor = part.end;
+        }
+
+        if cursor < interval.end {
+            let identity = Interval::new(cursor, interval.end);
+            parts.push((identity, identity));
+        }
+
+        parts
+    }
+
+    /// The values that the values of `interval` map to.
+    #[must_use]
+    pub fn map_interval(&self, interval: impl Into<Interval<T>>) -> IntervalSet<T> {
+        self.split(interval.into())
+            .into_iter()
+            .map(|(_, mapped)| mapped)
+            .collect()
+    }
+
+    /// The values that the values of `set` map to.
+    #[must_use]
+    pub fn map_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        set.iter()
+            .flat_map(|interval| self.split(*interval))
+            .map(|(_, mapped)| mapped)
+            .collect()
+    }
+
+    /// Composes two maps into one that maps a value like `next.get(self.get(value))`.
+    #[must_use]
+    pub fn then(&self, next: &Self) -> Self {
+        let mut entries = vec![];
+
+        for (source, destination) in &self.entries {
+            let image = Interval::new(
+                *destination,
+                translate(source.end, source.start, *destination),
+            );
+            for (part, mapped) in next.split(image) {
+                let start = translate(part.start, *destination, source.start);
+                let end = translate(part.end, *destination, source.start);
+                entries.push((Interval::new(start, end), mapped.start));
+            }
+        }
+
+        // values that `self` maps to themselves are only mapped by `next`.
+        let sources = self.sources();
+        for (source, destination) in &next.entries {
+            for part in IntervalSet::from(*source).difference(&sources).iter() {
+                entries.push((*part, translate(part.start, source.start, *destination)));
+            }
+        }
+
+        entries.sort_unstable();
+        Self { entries }
+    }
+}
+
+impl<T, I> FromIterator<(I, T)> for IntervalMap<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+    I: Into<Interval<T>>,
+{
+    fn from_iter<It: IntoIterator<Item = (I, T)>>(iter: It) -> Self {
+        let mut map = Self::new();
+        for (source, destination) in iter {
+            map.insert(source.into(), destination);
+        }
+        map
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Interval, IntervalMap, IntervalSet};
+
+    fn set(intervals: &[(u64, u64)]) -> IntervalSet<u64> {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    #[test]
+    fn contains_values() {
+        let interval = Interval::from_len(5, 15);
+        for i in 0..=20 {
+            assert_eq!(interval.contains(&i), (5..=19).contains(&i));
+        }
+        assert_eq!(interval.len(), 15);
+        assert_eq!(Interval::new(3, 1).len(), 0);
+        assert!(Interval::new(3, 3).is_empty());
+    }
+
+    #[test]
+    fn overlaps_intervals() {
+        assert!(Interval::from_len(5, 15).overlaps(&Interval::from_len(0, 20)));
+        assert!(!Interval::from_len(5, 15).overlaps(&Interval::from_len(20, 20)));
+        assert!(!Interval::new(0, 5).overlaps(&Interval::new(5, 5)));
+    }
+
+    #[test]
+    fn intersects_intervals() {
+        let a = Interval::from_len(0, 10);
+        let b = Interval::from_len(5, 15);
+        assert_eq!(a.intersection(&b), Some(Interval::new(5, 10)));
+        assert_eq!(b.intersection(&a), Some(Interval::new(5, 10)));
+        assert_eq!(a.intersection(&Interval::new(10, 12)), None);
+    }
+
+    #[test]
+    fn subtracts_intervals() {
+        let outer = Interval::from_len(0, 20);
+        let inner = Interval::from_len(5, 10);
+        assert_eq!(
+            outer.difference(&inner),
+            (Some(Interval::new(0, 5)), Some(Interval::new(15, 20)))
+        );
+        assert_eq!(inner.difference(&outer), (None, None));
+        assert_eq!(
+            inner.difference(&Interval::new(20, 30)),
+            (Some(inner), None)
+        );
+        assert_eq!(
+            Interval::new(20, 30).difference(&inner),
+            (None, Some(Interval::new(20, 30)))
+        );
+    }
+
+    #[test]
+    fn normalizes_sets() {
+        let normalized = set(&[(10, 12), (0, 5), (5, 7), (3, 4), (8, 8), (11, 15)]);
+        assert_eq!(normalized, set(&[(0, 7), (10, 15)]));
+        assert_eq!(normalized.intervals().len(), 2);
+        assert_eq!(normalized.len(), 12);
+        assert_eq!(normalized.min(), Some(0));
+        assert!(normalized.contains(&6));
+        assert!(!normalized.contains(&7));
+        assert!(normalized.contains(&14));
+        assert!(IntervalSet::<u64>::new().is_empty());
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a = set(&[(0, 10), (20, 30), (40, 50)]);
+        let b = set(&[(5, 25), (30, 35), (45, 60)]);
+
+        assert_eq!(a.union(&b), set(&[(0, 35), (40, 60)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25), (45, 50)]));
+        assert_eq!(a.difference(&b), set(&[(0, 5), (25, 30), (40, 45)]));
+        assert_eq!(b.difference(&a), set(&[(10, 20), (30, 35), (50, 60)]));
+        assert_eq!(a.difference(&a), IntervalSet::new());
+
+        let mut c = a.clone();
+        c.insert(8..42);
+        assert_eq!(c, set(&[(0, 50)]));
+    }
+
+    #[test]
+    fn maps_intervals() {
+        let map: IntervalMap<u64> = [(98..100, 50), (50..98, 52)].into_iter().collect();
+
+        assert_eq!(map.get(79), 81);
+        assert_eq!(map.get(98), 50);
+        assert_eq!(map.get(100), 100);
+        assert_eq!(map.map_interval(79..93), set(&[(81, 95)]));
+        assert_eq!(map.map_interval(40..105), set(&[(40, 100), (100, 105)]));
+        assert_eq!(
+            map.map_set(&set(&[(0, 3), (97, 99)])),
+            set(&[(0, 3), (50, 51), (99, 100)])
+        );
+    }
+
+    #[test]
+    fn keeps_earlier_entries() {
+        let mut map = IntervalMap::new();
+        map.insert(Interval::new(10, 20), 100);
+        map.insert(Interval::new(0, 30), 1000);
+
+        assert_eq!(map.get(15), 105);
+        assert_eq!(map.get(5), 1005);
+        assert_eq!(map.get(25), 1025);
+        assert_eq!(map.entries().count(), 3);
+    }
+
+    #[test]
+    fn composes_maps() {
+        let first: IntervalMap<u64> = [(0..10, 100), (20..30, 5)].into_iter().collect();
+        let second: IntervalMap<u64> = [(0..8, 50), (100..105, 0), (200..210, 300)]
+            .into_iter()
+            .collect();
+        let composed = first.then(&second);
+
+        for value in 0..250 {
+            assert_eq!(
+                composed.get(value),
+                second.get(first.get(value)),
+                "value {value}"
+            );
+        }
+
+        let values = set(&[(0, 40), (100, 110), (195, 215)]);
+        assert_eq!(
+            composed.map_set(&values),
+            second.map_set(&first.map_set(&values))
+        );
+    }
+}
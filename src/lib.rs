@@ -1,6 +1,17 @@
+pub mod cycles;
 mod day;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod intervals;
+mod puzzle_id;
+mod solution;
 pub mod template;
+mod year;
 pub use day::*;
+pub use puzzle_id::*;
+pub use solution::*;
+pub use year::*;
 
 pub mod parsers {
     use nom::bytes::complete::tag;
@@ -1,65 +1,150 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, bench_compare, check_answers, download, examples, read, scaffold, solve, stars, watch,
+};
 use args::{parse, AppArguments};
+use std::path::Path;
 
 mod args {
-    use std::process;
+    use std::path::PathBuf;
 
-    use advent_of_code::Day;
+    use advent_of_code::template::commands::{
+        all::DaySelection, scaffold::ScaffoldArgs, solve::InputArgs, BenchArgs, LimitArgs,
+    };
+    use advent_of_code::template::{runner::OutputFormat, Error};
+    use advent_of_code::{PuzzleId, Year};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Examples {
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            options: ScaffoldArgs,
+            puzzle: PuzzleId,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             time: bool,
             submit: Option<u8>,
+            bench: BenchArgs,
+            format: OutputFormat,
+            input: InputArgs,
+            limits: LimitArgs,
+            watch: bool,
+        },
+        Stars {
+            year: Year,
+            leaderboard: Option<PathBuf>,
+            user_id: Option<String>,
+        },
+        BenchCompare {
+            year: Year,
+            baseline: Option<String>,
+            threshold: f64,
+        },
+        CheckAnswers {
+            year: Year,
+            release: bool,
         },
         All {
+            year: Year,
             release: bool,
             time: bool,
+            bench: BenchArgs,
+            format: OutputFormat,
+            limits: LimitArgs,
+            selection: DaySelection,
         },
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    pub fn parse() -> Result<AppArguments, Error> {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                year: parse_year(&mut args)?,
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                bench: parse_bench_args(&mut args)?,
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                limits: parse_limit_args(&mut args)?,
+                selection: parse_day_selection(&mut args)?,
+            },
+            Some("bench-compare") => AppArguments::BenchCompare {
+                year: parse_year(&mut args)?,
+                baseline: args.opt_value_from_str("--baseline")?,
+                threshold: args.opt_value_from_str("--threshold")?.unwrap_or(10.0),
+            },
+            Some("check-answers") => AppArguments::CheckAnswers {
+                year: parse_year(&mut args)?,
+                release: args.contains("--release"),
             },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
+            },
+            Some("examples") => AppArguments::Examples {
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                options: ScaffoldArgs {
+                    template: args.opt_value_from_str("--template")?,
+                    return_type: args.opt_value_from_str("--return-type")?,
+                    dry_run: args.contains("--dry-run"),
+                    force: args.contains("--force"),
+                },
+                puzzle: parse_puzzle(&mut args)?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+            Some(command @ ("solve" | "watch")) => AppArguments::Solve {
+                watch: command == "watch" || args.contains("--watch"),
+                puzzle: parse_puzzle(&mut args)?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 time: args.contains("--time"),
+                bench: parse_bench_args(&mut args)?,
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                input: InputArgs {
+                    input: args.opt_value_from_str("--input")?,
+                    example: args.opt_value_from_str("--example")?,
+                },
+                limits: parse_limit_args(&mut args)?,
+            },
+            Some("stars") => AppArguments::Stars {
+                year: parse_year(&mut args)?,
+                leaderboard: args.opt_value_from_str("--leaderboard")?,
+                user_id: args.opt_value_from_str("--user-id")?,
             },
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
+            Some(x) => return Err(Error::Usage(format!("unknown command: {x}"))),
+            None => return Err(Error::Usage("no command specified.".into())),
+        };
+
+        if let AppArguments::Solve {
+            watch: true,
+            submit,
+            input,
+            ..
+        } = &app_args
+        {
+            if submit.is_some() {
+                return Err(Error::Usage(
+                    "`--submit` can not be used while watching.".into(),
+                ));
             }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
+            if input.input.as_deref() == Some("-") {
+                return Err(Error::Usage(
+                    "`--input -` can not be used while watching, stdin can only be read once."
+                        .into(),
+                ));
             }
-        };
+        }
 
         let remaining = args.finish();
         if !remaining.is_empty() {
@@ -68,25 +153,113 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Reads `--year`, falls back to `AOC_YEAR`.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Error> {
+        match args.opt_value_from_str("--year")? {
+            Some(year) => Ok(year),
+            None => Year::from_env().ok_or_else(|| {
+                Error::Usage(
+                    "no year set, pass `--year` or set \"AOC_YEAR\" in \".cargo/config.toml\"."
+                        .into(),
+                )
+            }),
+        }
+    }
+
+    fn parse_puzzle(args: &mut pico_args::Arguments) -> Result<PuzzleId, Error> {
+        let year = parse_year(args)?;
+        Ok(PuzzleId::new(year, args.free_from_str()?))
+    }
+
+    /// Reads the optional day selector, e.g. `cargo all 1-5,8,10..`, after all flags.
+    fn parse_day_selection(
+        args: &mut pico_args::Arguments,
+    ) -> Result<DaySelection, pico_args::Error> {
+        Ok(DaySelection {
+            skip: args.opt_value_from_str("--skip")?,
+            only_solved: args.contains("--only-solved"),
+            days: args.opt_free_from_str()?,
+        })
+    }
+
+    fn parse_limit_args(args: &mut pico_args::Arguments) -> Result<LimitArgs, Error> {
+        let timeout: Option<f64> = args.opt_value_from_str("--timeout")?;
+
+        if timeout.is_some_and(|secs| !secs.is_finite() || secs <= 0.0) {
+            return Err(Error::Usage(
+                "`--timeout` expects a positive number of seconds.".into(),
+            ));
+        }
+
+        Ok(LimitArgs { timeout })
+    }
+
+    fn parse_bench_args(args: &mut pico_args::Arguments) -> Result<BenchArgs, pico_args::Error> {
+        Ok(BenchArgs {
+            warmup: args.opt_value_from_str("--warmup")?,
+            keep_outliers: args.contains("--keep-outliers"),
+        })
+    }
 }
 
 fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
-        }
-        Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
-                day,
-                release,
-                time,
-                submit,
-            } => solve::handle(day, release, time, submit),
-        },
-    };
+    let result = parse().and_then(|args| match args {
+        AppArguments::All {
+            year,
+            release,
+            time,
+            bench,
+            format,
+            limits,
+            selection,
+        } => all::handle(year, release, time, &bench, &selection, format, limits),
+        AppArguments::BenchCompare {
+            year,
+            baseline,
+            threshold,
+        } => bench_compare::handle(year, baseline.as_deref(), threshold),
+        AppArguments::CheckAnswers { year, release } => check_answers::handle(year, release),
+        AppArguments::Download { puzzle } => download::handle(puzzle),
+        AppArguments::Examples { puzzle } => examples::handle(puzzle),
+        AppArguments::Read { puzzle } => read::handle(puzzle),
+        AppArguments::Scaffold { puzzle, options } => scaffold::handle(puzzle, &options),
+        AppArguments::Solve {
+            puzzle,
+            release,
+            time,
+            submit,
+            bench,
+            format,
+            input,
+            limits,
+            watch: false,
+        } => solve::handle(
+            puzzle, release, time, submit, &bench, format, &input, limits,
+        ),
+        AppArguments::Solve {
+            puzzle,
+            release,
+            time,
+            submit,
+            bench,
+            format,
+            input,
+            limits,
+            watch: true,
+        } => watch::handle(puzzle, input.input.as_deref().map(Path::new), || {
+            solve::handle(
+                puzzle, release, time, submit, &bench, format, &input, limits,
+            )
+        }),
+        AppArguments::Stars {
+            year,
+            leaderboard,
+            user_id,
+        } => stars::handle(year, leaderboard.as_deref(), user_id.as_deref()),
+    });
+
+    if let Err(e) = result {
+        e.exit();
+    }
 }
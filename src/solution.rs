@@ -0,0 +1,42 @@
+use std::fmt::Display;
+
+/// A solution that parses its input once and solves both parts from the parsed value.
+///
+/// Register an implementation with `solution!(<day>, <type>)`, the runner then times
+/// [`Solution::parse`] separately from the two parts. The `Send` and `'static` bounds
+/// allow the runner to move a step to its own thread when a `--timeout` is set.
+///
+/// ```
+/// # use advent_of_code::Solution;
+/// struct Sum;
+///
+/// impl Solution for Sum {
+///     type Parsed = Vec<u32>;
+///     type Error = std::num::ParseIntError;
+///     type Answer = u32;
+///
+///     fn parse(input: &str) -> Result<Self::Parsed, Self::Error> {
+///         input.lines().map(str::parse).collect()
+///     }
+///
+///     fn part_one(numbers: &Self::Parsed) -> Option<Self::Answer> {
+///         Some(numbers.iter().sum())
+///     }
+///
+///     fn part_two(numbers: &Self::Parsed) -> Option<Self::Answer> {
+///         numbers.iter().max().copied()
+///     }
+/// }
+/// ```
+pub trait Solution {
+    /// The input after parsing, shared by both parts.
+    type Parsed: Send + Sync + 'static;
+    /// The error returned if the input can not be parsed.
+    type Error: Display + Send + 'static;
+    /// The answer type of both parts.
+    type Answer: Display + Send + 'static;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Self::Error>;
+    fn part_one(parsed: &Self::Parsed) -> Option<Self::Answer>;
+    fn part_two(parsed: &Self::Parsed) -> Option<Self::Answer>;
+}
@@ -1,127 +1,89 @@
-/// Wrapper module around the "aoc-cli" command-line.
-use std::{
-    fmt::Display,
-    process::{Command, Output, Stdio},
-};
+/// Commands that interact with the Advent of Code website, see [`AocClient`].
+use std::{fmt::Display, fs, path::Path};
 
-use crate::Day;
+use crate::template::aoc_client::{AocClient, Verdict};
+use crate::template::get_data_path;
+use crate::PuzzleId;
 
 #[derive(Debug)]
 pub enum AocCommandError {
-    CommandNotFound,
-    CommandNotCallable,
-    BadExitStatus(Output),
+    /// No session cookie in `AOC_SESSION` or `~/.adventofcode.session`.
+    SessionNotFound,
+    /// The server rejected the session cookie.
+    AuthFailure,
+    /// The server asked us to slow down.
+    RateLimited,
+    HttpStatus(u16),
+    Transport(String),
     IoError,
 }
 
 impl Display for AocCommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
-            AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-            AocCommandError::BadExitStatus(_) => {
-                write!(f, "aoc-cli exited with a non-zero status.")
+            AocCommandError::SessionNotFound => write!(
+                f,
+                "no session cookie found in \"AOC_SESSION\" or \"~/.adventofcode.session\"."
+            ),
+            AocCommandError::AuthFailure => {
+                write!(f, "the session cookie was rejected, it might have expired.")
             }
+            AocCommandError::RateLimited => {
+                write!(f, "too many requests, please wait before trying again.")
+            }
+            AocCommandError::HttpStatus(status) => {
+                write!(f, "the server responded with status {status}.")
+            }
+            AocCommandError::Transport(e) => write!(f, "could not reach the server: {e}"),
             AocCommandError::IoError => write!(f, "could not write output files to file system."),
         }
     }
 }
 
+/// Checks that a session cookie is configured.
 pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
-        .arg("-V")
-        .output()
-        .map_err(|_| AocCommandError::CommandNotFound)?;
-    Ok(())
+    AocClient::from_env().map(|_| ())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
-
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
-
-    call_aoc_cli(&args)
-}
-
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
-
-    let args = build_args(
-        "download",
-        &[
-            "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
-        ],
-        day,
-    );
-
-    let output = call_aoc_cli(&args)?;
-    println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
+/// Downloads the puzzle description, writes it to the puzzle file and prints it.
+pub fn read(puzzle: PuzzleId) -> Result<(), AocCommandError> {
+    let description = AocClient::from_env()?.fetch_puzzle(puzzle)?;
+    write_file(&get_data_path("puzzles", puzzle, "md"), &description)?;
+    println!("{description}");
+    Ok(())
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
-    // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
-    args.push(part.to_string());
-    args.push(result.to_string());
-    call_aoc_cli(&args)
-}
+pub fn download(puzzle: PuzzleId) -> Result<(), AocCommandError> {
+    let client = AocClient::from_env()?;
+    let input_path = get_data_path("inputs", puzzle, "txt");
+    let puzzle_path = get_data_path("puzzles", puzzle, "md");
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
-}
+    let input = client.fetch_input(puzzle)?;
+    write_file(&input_path, &input)?;
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
-}
+    let description = client.fetch_puzzle(puzzle)?;
+    write_file(&puzzle_path, &description)?;
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
-    let mut cmd_args = args.to_vec();
-
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
-    }
-
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
-
-    cmd_args
+pub fn submit(puzzle: PuzzleId, part: u8, result: &str) -> Result<Verdict, AocCommandError> {
+    AocClient::from_env()?.submit(puzzle, part, result)
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
-    // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
-        .args(args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .output()
-        .map_err(|_| AocCommandError::CommandNotCallable)?;
-
-    if output.status.success() {
-        Ok(output)
-    } else {
-        Err(AocCommandError::BadExitStatus(output))
+/// Writes a file, creating the year's data directories if needed.
+fn write_file(path: &Path, contents: &str) -> Result<(), AocCommandError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|_| AocCommandError::IoError)?;
     }
+    fs::write(path, contents).map_err(|_| AocCommandError::IoError)
 }
@@ -0,0 +1,584 @@
+/// Minimal HTTP client for the Advent of Code website.
+/// Replaces the external "aoc-cli" binary for downloading inputs, reading puzzles and submitting answers.
+use std::{env, fs, path::PathBuf, time::Duration};
+
+use crate::template::aoc_cli::AocCommandError;
+use crate::PuzzleId;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_FILE: &str = ".adventofcode.session";
+const USER_AGENT: &str = concat!(
+    "github.com/fspoettel/advent-of-code-rust ",
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION")
+);
+
+/// The server's response to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// Wrong answers contain the time the server asks to wait before the next submission.
+    TooHigh(Option<Duration>),
+    TooLow(Option<Duration>),
+    Incorrect(Option<Duration>),
+    /// An answer was submitted too recently, the server asks to wait before trying again.
+    Wait(Duration),
+    /// The part was already solved or is not unlocked yet.
+    WrongLevel,
+    /// The response could not be interpreted, contains the response text.
+    Unknown(String),
+}
+
+impl Verdict {
+    /// Returns how long to wait before the next submission, if the server asked for it.
+    #[must_use]
+    pub fn wait(&self) -> Option<Duration> {
+        match self {
+            Verdict::TooHigh(wait) | Verdict::TooLow(wait) | Verdict::Incorrect(wait) => *wait,
+            Verdict::Wait(duration) => Some(*duration),
+            _ => None,
+        }
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    /// Creates a client from the environment:
+    ///  1. the session cookie is read from `AOC_SESSION` or `~/.adventofcode.session`.
+    ///  2. the base URL can be overridden with `AOC_BASE_URL`.
+    pub fn from_env() -> Result<Self, AocCommandError> {
+        let session = find_session().ok_or(AocCommandError::SessionNotFound)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &session))
+    }
+
+    fn day_url(&self, puzzle: PuzzleId) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    pub fn fetch_input(&self, puzzle: PuzzleId) -> Result<String, AocCommandError> {
+        self.get(&format!("{}/input", self.day_url(puzzle)))
+    }
+
+    /// Fetches the puzzle description and converts it to markdown.
+    pub fn fetch_puzzle(&self, puzzle: PuzzleId) -> Result<String, AocCommandError> {
+        let html = self.get(&self.day_url(puzzle))?;
+        Ok(markdown::from_html(&html))
+    }
+
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<Verdict, AocCommandError> {
+        let response = ureq::post(&format!("{}/answer", self.day_url(puzzle)))
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        let html = read_response(response)?;
+        Ok(parse_verdict(&markdown::article_text(&html)))
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocCommandError> {
+        let response = ureq::get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .call();
+
+        read_response(response)
+    }
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, AocCommandError> {
+    match response {
+        Ok(response) => response.into_string().map_err(|_| AocCommandError::IoError),
+        Err(ureq::Error::Status(status, _)) => Err(match status {
+            400 | 401 | 403 => AocCommandError::AuthFailure,
+            429 => AocCommandError::RateLimited,
+            status => AocCommandError::HttpStatus(status),
+        }),
+        Err(ureq::Error::Transport(e)) => Err(AocCommandError::Transport(e.to_string())),
+    }
+}
+
+fn find_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    let session = fs::read_to_string(PathBuf::from(home).join(SESSION_FILE)).ok()?;
+    Some(session.trim().to_string())
+}
+
+fn parse_verdict(text: &str) -> Verdict {
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("your answer is too high") {
+        Verdict::TooHigh(parse_retry_time(text))
+    } else if text.contains("your answer is too low") {
+        Verdict::TooLow(parse_retry_time(text))
+    } else if text.contains("That's not the right answer") {
+        Verdict::Incorrect(parse_retry_time(text))
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::Wait(parse_wait_time(text).unwrap_or(Duration::from_secs(60)))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown(text.to_string())
+    }
+}
+
+/// Parses durations like "You have 1m 5s left to wait."
+fn parse_wait_time(text: &str) -> Option<Duration> {
+    let (before, _) = text.split_once(" left to wait")?;
+    let (_, time) = before.rsplit_once("You have ")?;
+
+    time.split_whitespace()
+        .map(|part| {
+            let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Parses durations like "Please wait one minute before trying again." of wrong answers.
+fn parse_retry_time(text: &str) -> Option<Duration> {
+    const NUMBERS: [&str; 10] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    ];
+
+    let (before, _) = text.split_once(" before trying again")?;
+    let (_, time) = before.rsplit_once("wait ")?;
+    let (value, unit) = time.split_once(' ')?;
+    let value: u64 = match NUMBERS.iter().position(|n| value.eq_ignore_ascii_case(n)) {
+        Some(i) => i as u64 + 1,
+        None => value.parse().ok()?,
+    };
+
+    let seconds = match unit.trim_end_matches('s') {
+        "hour" => value * 3600,
+        "minute" => value * 60,
+        "second" => value,
+        _ => return None,
+    };
+    Some(Duration::from_secs(seconds))
+}
+
+/// Converts the puzzle descriptions of a puzzle page into markdown.
+/// Only the subset of HTML used on adventofcode.com is supported.
+mod markdown {
+    enum Node {
+        Element {
+            tag: String,
+            href: Option<String>,
+            children: Vec<Node>,
+        },
+        Text(String),
+    }
+
+    const VOID_TAGS: [&str; 5] = ["br", "hr", "img", "input", "meta"];
+
+    fn parse_attribute(tag: &str, name: &str) -> Option<String> {
+        let needle = format!("{name}=\"");
+        let start = tag.find(&needle)? + needle.len();
+        let end = tag[start..].find('"')? + start;
+        Some(decode_entities(&tag[start..end]))
+    }
+
+    fn decode_entities(s: &str) -> String {
+        s.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&apos;", "'")
+            .replace("&nbsp;", " ")
+            .replace("&amp;", "&")
+    }
+
+    /// Parses HTML into a list of nodes, tolerating unclosed tags.
+    fn parse(html: &str) -> Vec<Node> {
+        // (tag, href, children) of every open element, the root is the first entry.
+        let mut stack: Vec<(String, Option<String>, Vec<Node>)> =
+            vec![(String::new(), None, vec![])];
+        let mut rest = html;
+
+        while !rest.is_empty() {
+            let Some(start) = rest.find('<') else {
+                stack
+                    .last_mut()
+                    .unwrap()
+                    .2
+                    .push(Node::Text(decode_entities(rest)));
+                break;
+            };
+
+            if start > 0 {
+                let text = decode_entities(&rest[..start]);
+                stack.last_mut().unwrap().2.push(Node::Text(text));
+            }
+
+            let Some(end) = rest[start..].find('>').map(|end| start + end) else {
+                break;
+            };
+
+            let tag = &rest[start + 1..end];
+            rest = &rest[end + 1..];
+
+            if let Some(name) = tag.strip_prefix('/') {
+                let name = name.trim().to_lowercase();
+                if let Some(pos) = stack.iter().rposition(|(tag, _, _)| *tag == name) {
+                    while stack.len() > pos.max(1) {
+                        let (tag, href, children) = stack.pop().unwrap();
+                        stack.last_mut().unwrap().2.push(Node::Element {
+                            tag,
+                            href,
+                            children,
+                        });
+                    }
+                }
+                continue;
+            }
+
+            if tag.starts_with('!') || tag.starts_with('?') {
+                continue;
+            }
+
+            let name = tag
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default()
+                .to_lowercase();
+
+            let href = parse_attribute(tag, "href");
+
+            if VOID_TAGS.contains(&name.as_str()) || tag.ends_with('/') {
+                stack.last_mut().unwrap().2.push(Node::Element {
+                    tag: name,
+                    href,
+                    children: vec![],
+                });
+            } else if name == "script" || name == "style" {
+                // skip raw text elements entirely.
+                let closing = format!("</{name}>");
+                rest = rest
+                    .find(&closing)
+                    .map_or("", |i| &rest[i + closing.len()..]);
+            } else {
+                stack.push((name, href, vec![]));
+            }
+        }
+
+        while stack.len() > 1 {
+            let (tag, href, children) = stack.pop().unwrap();
+            stack.last_mut().unwrap().2.push(Node::Element {
+                tag,
+                href,
+                children,
+            });
+        }
+
+        stack.pop().unwrap().2
+    }
+
+    fn plain_text(nodes: &[Node]) -> String {
+        nodes
+            .iter()
+            .map(|node| match node {
+                Node::Text(text) => text.clone(),
+                Node::Element { children, .. } => plain_text(children),
+            })
+            .collect()
+    }
+
+    fn contains_tag(nodes: &[Node], name: &str) -> bool {
+        nodes.iter().any(|node| match node {
+            Node::Text(_) => false,
+            Node::Element { tag, children, .. } => tag == name || contains_tag(children, name),
+        })
+    }
+
+    fn render_inline(nodes: &[Node]) -> String {
+        nodes
+            .iter()
+            .map(|node| match node {
+                Node::Text(text) => text.replace('\n', " "),
+                Node::Element {
+                    tag,
+                    href,
+                    children,
+                } => match tag.as_str() {
+                    "code" if contains_tag(children, "em") => {
+                        format!("*`{}`*", plain_text(children))
+                    }
+                    "code" => format!("`{}`", plain_text(children)),
+                    "em" => format!("*{}*", render_inline(children)),
+                    "a" => match href {
+                        Some(href) => format!("[{}]({href})", render_inline(children)),
+                        None => render_inline(children),
+                    },
+                    "br" => "\n".into(),
+                    _ => render_inline(children),
+                },
+            })
+            .collect()
+    }
+
+    fn render_blocks(nodes: &[Node], blocks: &mut Vec<String>) {
+        for node in nodes {
+            let Node::Element { tag, children, .. } = node else {
+                continue;
+            };
+
+            match tag.as_str() {
+                "h2" => blocks.push(format!("## {}", render_inline(children).trim())),
+                "p" => blocks.push(render_inline(children).trim().to_string()),
+                "pre" => blocks.push(format!(
+                    "```\n{}\n```",
+                    plain_text(children).trim_end_matches('\n')
+                )),
+                "ul" | "ol" => {
+                    let items: Vec<String> = children
+                        .iter()
+                        .filter_map(|child| match child {
+                            Node::Element { tag, children, .. } if tag == "li" => {
+                                Some(format!("- {}", render_inline(children).trim()))
+                            }
+                            _ => None,
+                        })
+                        .collect();
+                    blocks.push(items.join("\n"));
+                }
+                _ => render_blocks(children, blocks),
+            }
+        }
+    }
+
+    fn find_articles<'a>(nodes: &'a [Node], articles: &mut Vec<&'a [Node]>) {
+        for node in nodes {
+            if let Node::Element { tag, children, .. } = node {
+                if tag == "article" {
+                    articles.push(children);
+                } else {
+                    find_articles(children, articles);
+                }
+            }
+        }
+    }
+
+    /// Converts every `<article>` of a puzzle page to markdown.
+    pub fn from_html(html: &str) -> String {
+        let nodes = parse(html);
+        let mut articles = vec![];
+        find_articles(&nodes, &mut articles);
+
+        let mut blocks = vec![];
+        for article in articles {
+            render_blocks(article, &mut blocks);
+        }
+
+        blocks.join("\n\n") + "\n"
+    }
+
+    /// Returns the plain text of every `<article>` of a page.
+    pub fn article_text(html: &str) -> String {
+        let nodes = parse(html);
+        let mut articles = vec![];
+        find_articles(&nodes, &mut articles);
+        articles.into_iter().map(plain_text).collect()
+    }
+
+    #[cfg(feature = "test_lib")]
+    mod tests {
+        use super::from_html;
+
+        #[test]
+        fn converts_puzzle_article() {
+            let html = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>Something is <em>wrong</em> with <a href="/2023/support">global snow</a> production.</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<ul><li>one &amp; two</li><li><code>a &lt; b</code></li></ul>
+<p>In this example, the sum is <code><em>142</em></code>.</p>
+</article>
+<p>To begin, <a href="1/input" target="_blank">get your puzzle input</a>.</p>
+</main></body></html>"#;
+
+            let expected = [
+                "## --- Day 1: Trebuchet?! ---",
+                "",
+                "Something is *wrong* with [global snow](/2023/support) production.",
+                "",
+                "```",
+                "1abc2",
+                "pqr3stu8vwx",
+                "```",
+                "",
+                "- one & two",
+                "- `a < b`",
+                "",
+                "In this example, the sum is *`142`*.",
+                "",
+            ]
+            .join("\n");
+
+            assert_eq!(from_html(html), expected);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_verdict, AocClient, Verdict};
+    use crate::template::aoc_cli::AocCommandError;
+    use crate::{Day, PuzzleId, Year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+        time::Duration,
+    };
+
+    /// Serves a single request with the given status and body, returns the raw request.
+    fn serve_once(status: u16, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            request.push_str(&String::from_utf8(request_body).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {status} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (url, handle)
+    }
+
+    fn puzzle(day: u8) -> PuzzleId {
+        PuzzleId::new(Year::__new_unchecked(2023), Day::new(day).unwrap())
+    }
+
+    #[test]
+    fn fetches_input_with_session() {
+        let (url, server) = serve_once(200, "1\n2\n3\n");
+        let client = AocClient::new(&url, "secret\n");
+
+        assert_eq!(client.fetch_input(puzzle(4)).unwrap(), "1\n2\n3\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2023/day/4/input "));
+        assert!(request.contains("session=secret\r\n"));
+    }
+
+    #[test]
+    fn maps_error_status() {
+        let (url, server) = serve_once(400, "Puzzle inputs differ by user.");
+        let res = AocClient::new(&url, "").fetch_input(puzzle(1));
+        assert!(matches!(res, Err(AocCommandError::AuthFailure)));
+        server.join().unwrap();
+
+        let (url, server) = serve_once(429, "");
+        let res = AocClient::new(&url, "").fetch_input(puzzle(1));
+        assert!(matches!(res, Err(AocCommandError::RateLimited)));
+        server.join().unwrap();
+
+        let (url, server) = serve_once(500, "");
+        let res = AocClient::new(&url, "").fetch_input(puzzle(1));
+        assert!(matches!(res, Err(AocCommandError::HttpStatus(500))));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn submits_answer() {
+        let (url, server) = serve_once(
+            200,
+            "<main><article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article></main>",
+        );
+        let client = AocClient::new(&url, "secret");
+
+        assert!(matches!(
+            client.submit(puzzle(2), 1, "42"),
+            Ok(Verdict::Correct)
+        ));
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2023/day/2/answer "));
+        assert!(request.ends_with("level=1&answer=42"));
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            parse_verdict("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh(None)
+        );
+        assert_eq!(
+            parse_verdict("That's not the right answer; your answer is too low. Please wait one minute before trying again."),
+            Verdict::TooLow(Some(Duration::from_secs(60)))
+        );
+        assert_eq!(
+            parse_verdict("That's not the right answer. If you're stuck, ... Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again."),
+            Verdict::Incorrect(Some(Duration::from_secs(300)))
+        );
+        assert_eq!(
+            parse_verdict("You gave an answer too recently. You have 1m 5s left to wait."),
+            Verdict::Wait(Duration::from_secs(65))
+        );
+        assert_eq!(
+            parse_verdict("You don't seem to be solving the right level."),
+            Verdict::WrongLevel
+        );
+    }
+}
@@ -0,0 +1,163 @@
+/// History of timed runs, stored as one JSON document per line in `data/<year>/benchmarks.ndjson`.
+/// Used by `cargo bench-compare` to detect regressions between runs.
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    process::Command,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::runner::PartRecord;
+use crate::template::{get_data_dir, submissions};
+use crate::{Day, Year};
+
+/// A single `cargo time` run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Run {
+    /// Unix timestamp in seconds.
+    pub timestamp: u64,
+    /// Abbreviated hash of the checked out commit, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    pub records: Vec<PartRecord>,
+}
+
+impl Run {
+    /// Creates a run of the current time and commit.
+    #[must_use]
+    pub fn new(records: Vec<PartRecord>) -> Self {
+        Self {
+            timestamp: submissions::now(),
+            commit: get_commit(),
+            records,
+        }
+    }
+
+    /// Returns the record of a day's part, if it was run.
+    #[must_use]
+    pub fn get(&self, day: Day, part: u8) -> Option<&PartRecord> {
+        self.records
+            .iter()
+            .find(|record| record.day == day && record.part == part)
+    }
+}
+
+#[must_use]
+pub fn get_path(year: Year) -> PathBuf {
+    get_data_dir(year).join("benchmarks.ndjson")
+}
+
+fn get_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Appends a run to the history of `year`.
+pub fn append(year: Year, run: &Run) -> io::Result<()> {
+    let path = get_path(year);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let line = serde_json::to_string(run).map_err(io::Error::from)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Loads every run of `year`, oldest first. Returns an empty history if none exists yet.
+pub fn load(year: Year) -> io::Result<Vec<Run>> {
+    match fs::read_to_string(get_path(year)) {
+        Ok(s) => s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(io::Error::from))
+            .collect(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+/// Finds the run to compare the latest run against.
+///
+/// With a `baseline`, this is the most recent earlier run of a commit starting with `baseline`.
+/// Otherwise, it is the most recent run of an earlier commit, or the previous run if there is none.
+#[must_use]
+pub fn find_baseline<'a>(runs: &'a [Run], baseline: Option<&str>) -> Option<&'a Run> {
+    let (latest, earlier) = runs.split_last()?;
+
+    match baseline {
+        Some(baseline) => earlier.iter().rev().find(|run| {
+            run.commit
+                .as_deref()
+                .is_some_and(|commit| commit.starts_with(baseline))
+        }),
+        None => earlier
+            .iter()
+            .rev()
+            .find(|run| run.commit.is_some() && run.commit != latest.commit)
+            .or_else(|| earlier.last()),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_baseline, Run};
+
+    fn run(timestamp: u64, commit: Option<&str>) -> Run {
+        Run {
+            timestamp,
+            commit: commit.map(Into::into),
+            records: vec![],
+        }
+    }
+
+    #[test]
+    fn finds_previous_commit() {
+        let runs = [
+            run(1, Some("aaa")),
+            run(2, Some("bbb")),
+            run(3, Some("ccc")),
+            run(4, Some("ccc")),
+        ];
+        assert_eq!(find_baseline(&runs, None), Some(&runs[1]));
+    }
+
+    #[test]
+    fn falls_back_to_previous_run() {
+        let runs = [run(1, None), run(2, None), run(3, None)];
+        assert_eq!(find_baseline(&runs, None), Some(&runs[1]));
+
+        let runs = [run(1, Some("aaa")), run(2, Some("aaa"))];
+        assert_eq!(find_baseline(&runs, None), Some(&runs[0]));
+
+        assert_eq!(find_baseline(&runs[..1], None), None);
+    }
+
+    #[test]
+    fn finds_named_baseline() {
+        let runs = [
+            run(1, Some("abc123")),
+            run(2, Some("def456")),
+            run(3, Some("abc123")),
+        ];
+        assert_eq!(find_baseline(&runs, Some("abc")), Some(&runs[0]));
+        assert_eq!(find_baseline(&runs, Some("fff")), None);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let run = run(10, Some("abc123"));
+        let json = serde_json::to_string(&run).unwrap();
+        assert_eq!(json, r#"{"timestamp":10,"commit":"abc123","records":[]}"#);
+        assert_eq!(serde_json::from_str::<Run>(&json).unwrap(), run);
+    }
+}
@@ -0,0 +1,243 @@
+/// Renders the benchmark timings as a bar chart, which is linked from the benchmarking table.
+/// Days are drawn as horizontal bars on a logarithmic scale, stacked by parse, part 1 and part 2.
+use std::{fs, io, path::Path};
+
+use crate::template::readme_benchmarks::{parse_duration, Timings};
+
+pub static CHART_PATH: &str = ".assets/benchmarks.svg";
+
+const WIDTH: f64 = 640.0;
+const LABEL_WIDTH: f64 = 64.0;
+const MARGIN: f64 = 16.0;
+const LEGEND_HEIGHT: f64 = 32.0;
+const AXIS_HEIGHT: f64 = 32.0;
+const ROW_HEIGHT: f64 = 22.0;
+const BAR_HEIGHT: f64 = 14.0;
+
+const BACKGROUND: &str = "#0f0f23";
+const FOREGROUND: &str = "#cccccc";
+const GRID: &str = "#333340";
+
+/// Name and color of every bar segment, in drawing order.
+const SEGMENTS: [(&str, &str); 3] = [
+    ("Parse", "#666677"),
+    ("Part 1", "#9999cc"),
+    ("Part 2", "#ffff66"),
+];
+
+/// Scales durations in nanoseconds to the x axis, one tick per power of ten.
+struct LogScale {
+    min_exp: i32,
+    max_exp: i32,
+}
+
+impl LogScale {
+    fn new(values: impl Iterator<Item = f64>) -> Self {
+        let (min, max) = values
+            .filter(|v| *v > 0.0)
+            .fold((f64::MAX, f64::MIN), |(min, max), v| {
+                (min.min(v), max.max(v))
+            });
+
+        if min > max {
+            return Self {
+                min_exp: 0,
+                max_exp: 1,
+            };
+        }
+
+        let min_exp = min.log10().floor() as i32;
+        let max_exp = (max.log10().ceil() as i32).max(min_exp + 1);
+        Self { min_exp, max_exp }
+    }
+
+    fn x(&self, nanos: f64) -> f64 {
+        let plot_width = WIDTH - LABEL_WIDTH - 2.0 * MARGIN;
+        let fraction = (nanos.max(1.0).log10() - f64::from(self.min_exp))
+            / f64::from(self.max_exp - self.min_exp);
+        LABEL_WIDTH + MARGIN + fraction.clamp(0.0, 1.0) * plot_width
+    }
+}
+
+/// Formats a power of ten in nanoseconds as a tick label, e.g. `10µs`.
+fn format_tick(exp: i32) -> String {
+    let (unit, unit_exp) = match exp {
+        ..=2 => ("ns", 0),
+        3..=5 => ("µs", 3),
+        6..=8 => ("ms", 6),
+        _ => ("s", 9),
+    };
+    format!("{}{unit}", 10_u64.pow((exp - unit_exp).unsigned_abs()))
+}
+
+/// Returns the durations of parse, part 1 and part 2 in nanoseconds, missing parts are zero.
+fn segments(timing: &Timings) -> [f64; 3] {
+    [&timing.parse, &timing.part_1, &timing.part_2]
+        .map(|time| time.as_deref().and_then(parse_duration).unwrap_or(0.0))
+}
+
+#[must_use]
+pub fn render(timings: &[Timings]) -> String {
+    let rows: Vec<[f64; 3]> = timings.iter().map(segments).collect();
+
+    // on a log scale a stack is drawn from the cumulative sums, so each bar ends at the day's total.
+    let ends: Vec<[f64; 3]> = rows
+        .iter()
+        .map(|row| {
+            let mut sum = 0.0;
+            row.map(|nanos| {
+                sum += nanos;
+                sum
+            })
+        })
+        .collect();
+
+    let scale = LogScale::new(ends.iter().flatten().copied());
+
+    let plot_top = MARGIN + LEGEND_HEIGHT;
+    let plot_bottom = plot_top + ROW_HEIGHT * timings.len() as f64;
+    let height = plot_bottom + AXIS_HEIGHT + MARGIN;
+
+    let mut svg = String::new();
+    let mut line = |s: String| {
+        svg.push_str(&s);
+        svg.push('\n');
+    };
+
+    line(format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}" font-family="monospace" font-size="12">"#
+    ));
+    line(format!(
+        r#"<rect width="100%" height="100%" fill="{BACKGROUND}"/>"#
+    ));
+
+    let mut legend_x = LABEL_WIDTH + MARGIN;
+    for (name, color) in SEGMENTS {
+        line(format!(
+            r#"<rect x="{legend_x:.1}" y="{MARGIN:.1}" width="12" height="12" fill="{color}"/>"#
+        ));
+        line(format!(
+            r#"<text x="{:.1}" y="{:.1}" fill="{FOREGROUND}">{name}</text>"#,
+            legend_x + 18.0,
+            MARGIN + 10.0
+        ));
+        legend_x += 96.0;
+    }
+
+    for exp in scale.min_exp..=scale.max_exp {
+        let x = scale.x(10_f64.powi(exp));
+        line(format!(
+            r#"<line x1="{x:.1}" y1="{plot_top:.1}" x2="{x:.1}" y2="{plot_bottom:.1}" stroke="{GRID}"/>"#
+        ));
+        line(format!(
+            r#"<text x="{x:.1}" y="{:.1}" fill="{FOREGROUND}" text-anchor="middle">{}</text>"#,
+            plot_bottom + 18.0,
+            format_tick(exp)
+        ));
+    }
+
+    for (i, (timing, ends)) in timings.iter().zip(&ends).enumerate() {
+        let y = plot_top + ROW_HEIGHT * i as f64;
+
+        line(format!(
+            r#"<text x="{LABEL_WIDTH:.1}" y="{:.1}" fill="{FOREGROUND}" text-anchor="end">Day {}</text>"#,
+            y + ROW_HEIGHT / 2.0 + 4.0,
+            timing.puzzle.day
+        ));
+
+        let mut start = LABEL_WIDTH + MARGIN;
+        for ((name, color), (end, time)) in
+            SEGMENTS.iter().zip(
+                ends.iter()
+                    .zip([&timing.parse, &timing.part_1, &timing.part_2]),
+            )
+        {
+            let Some(time) = time else { continue };
+            let x = scale.x(*end);
+            if x <= start {
+                continue;
+            }
+
+            line(format!(
+                r#"<rect x="{start:.1}" y="{:.1}" width="{:.1}" height="{BAR_HEIGHT:.1}" fill="{color}"><title>Day {} {name}: {time}</title></rect>"#,
+                y + (ROW_HEIGHT - BAR_HEIGHT) / 2.0,
+                x - start,
+                timing.puzzle.day
+            ));
+            start = x;
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Writes the chart of `timings` to [`CHART_PATH`].
+pub fn write(timings: &[Timings]) -> io::Result<()> {
+    let path = Path::new(CHART_PATH);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, render(timings))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_tick, render, LogScale};
+    use crate::template::readme_benchmarks::Timings;
+    use crate::{day, Day, PuzzleId, Year};
+
+    fn timing(day: Day, parse: Option<&str>, part_1: &str, part_2: Option<&str>) -> Timings {
+        Timings {
+            puzzle: PuzzleId::new(Year::__new_unchecked(2023), day),
+            parse: parse.map(Into::into),
+            part_1: Some(part_1.into()),
+            part_2: part_2.map(Into::into),
+            memory: None,
+            total_nanos: 0.0,
+        }
+    }
+
+    #[test]
+    fn formats_ticks() {
+        assert_eq!(format_tick(0), "1ns");
+        assert_eq!(format_tick(2), "100ns");
+        assert_eq!(format_tick(4), "10µs");
+        assert_eq!(format_tick(6), "1ms");
+        assert_eq!(format_tick(10), "10s");
+    }
+
+    #[test]
+    fn scales_by_decade() {
+        let scale = LogScale::new([350.0, 41_500.0, 2e6].into_iter());
+        assert_eq!((scale.min_exp, scale.max_exp), (2, 7));
+        assert!(scale.x(100.0) < scale.x(1_000.0));
+        let decade = scale.x(1e4) - scale.x(1e3);
+        assert!((scale.x(1e5) - scale.x(1e4) - decade).abs() < 1e-9);
+    }
+
+    #[test]
+    fn renders_stacked_bars() {
+        let svg = render(&[
+            timing(day!(1), None, "41.7µs", Some("1.5ms")),
+            timing(day!(3), Some("2.0µs"), "350.0ns", None),
+        ]);
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains(">Day 01</text>"));
+        assert!(svg.contains(">Day 03</text>"));
+        assert!(svg.contains("<title>Day 01 Part 2: 1.5ms</title>"));
+        assert!(svg.contains("<title>Day 03 Parse: 2.0µs</title>"));
+        assert!(!svg.contains("Day 01 Parse"));
+        assert!(!svg.contains("Day 03 Part 2"));
+        assert_eq!(svg.matches("<title>").count(), 4);
+    }
+
+    #[test]
+    fn renders_empty_chart() {
+        let svg = render(&[]);
+        assert!(svg.contains(">1ns</text>"));
+        assert!(!svg.contains("<title>"));
+    }
+}
@@ -1,254 +1,380 @@
-use std::io;
+use std::env;
+use std::process::{Command, Stdio};
 
+use super::{check_status, year_from_args};
 use crate::template::{
+    alloc_stats::{self, format_bytes},
+    bench_history::{self, Run},
+    commands::{BenchArgs, LimitArgs},
     readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    registry::Registry,
+    runner::{OutputFormat, PartRecord, PARSE_PART},
+    try_read_file, Context, Error, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{all_days, Day, DaySet, PuzzleId, Year};
+
+/// Restricts the days that are run by `all`.
+#[derive(Debug, Default, Clone)]
+pub struct DaySelection {
+    /// Days to run, every day of the year if [`None`].
+    pub days: Option<DaySet>,
+    pub skip: Option<DaySet>,
+    /// Leave out days without a solution instead of reporting them as not solved.
+    pub only_solved: bool,
+}
 
-pub fn handle(is_release: bool, is_timed: bool) {
-    let mut timings: Vec<Timings> = vec![];
+impl DaySelection {
+    /// Builds the command-line arguments understood by [`DaySelection::from_args`].
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
+        if let Some(days) = &self.days {
+            args.push("--days".into());
+            args.push(days.to_string());
         }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
-
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        if let Some(skip) = &self.skip {
+            args.push("--skip".into());
+            args.push(skip.to_string());
+        }
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+        if self.only_solved {
+            args.push("--only-solved".into());
         }
-    });
 
-    if is_timed {
-        let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+        args
+    }
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+    fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
-                Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
-                }
-            }
+        let day_set = |name: &str| {
+            args.iter()
+                .position(|x| x == name)
+                .and_then(|i| args.get(i + 1))
+                .and_then(|x| x.parse().ok())
+        };
+
+        Self {
+            days: day_set("--days"),
+            skip: day_set("--skip"),
+            only_solved: args.iter().any(|x| x == "--only-solved"),
         }
     }
-}
 
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    Parser(String),
-    IO(io::Error),
+    fn includes(&self, day: Day) -> bool {
+        self.days.as_ref().is_none_or(|days| days.contains(day))
+            && !self.skip.as_ref().is_some_and(|skip| skip.contains(day))
+    }
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+/// Builds and runs the `all` binary, which runs the selected solutions of `year` in-process.
+pub fn handle(
+    year: Year,
+    is_release: bool,
+    is_timed: bool,
+    bench: &BenchArgs,
+    selection: &DaySelection,
+    format: OutputFormat,
+    limits: LimitArgs,
+) -> Result<(), Error> {
+    let mut args: Vec<String> = vec!["run".into(), "--quiet".into(), "--bin".into(), "all".into()];
+
+    if is_release {
+        args.push("--release".into());
+    }
+
+    if alloc_stats::is_enabled() {
+        args.push("--features".into());
+        args.push("alloc_stats".into());
+    }
+
+    // mirror flags to the `all` binary.
+    args.push("--".into());
+    args.push("--year".into());
+    args.push(year.to_string());
+
+    if is_release {
+        args.push("--release".into());
+    }
+
+    if is_timed {
+        args.push("--time".into());
+        args.extend(bench.to_args());
     }
+
+    args.extend(selection.to_args());
+    args.extend(limits.to_args());
+
+    args.push("--format".into());
+    args.push(format.as_str().into());
+
+    let status = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .context("failed to run cargo")?;
+
+    check_status(&args, status)
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Entry point of the `all` binary.
+pub fn main(registry: &Registry) -> Result<(), Error> {
+    let args: Vec<String> = env::args().collect();
+    let is_release = args.iter().any(|x| x == "--release");
+    let is_timed = args.iter().any(|x| x == "--time");
+
+    run(
+        registry,
+        year_from_args()?,
+        &DaySelection::from_args(),
+        is_release,
+        is_timed,
+        OutputFormat::from_args(),
+    )
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
-    };
+/// Runs the selected days, days whose input cannot be read are skipped and reported at the end.
+/// A single missing input keeps its [`Error::MissingInput`], so the exit code is the same as for `cargo solve`.
+fn run(
+    registry: &Registry,
+    year: Year,
+    selection: &DaySelection,
+    is_release: bool,
+    is_timed: bool,
+    format: OutputFormat,
+) -> Result<(), Error> {
+    let is_text = format == OutputFormat::Text;
+    let mut timings: Vec<Timings> = vec![];
+    let mut records: Vec<PartRecord> = vec![];
+    let mut errors: Vec<Error> = vec![];
+
+    let days = all_days(year)
+        .filter(|&day| selection.includes(day))
+        .map(|day| (day, registry.get(PuzzleId::new(year, day))))
+        .filter(|(_, solution)| solution.is_some() || !selection.only_solved);
+
+    days.enumerate().for_each(|(i, (day, solution))| {
+        if is_text {
+            if i > 0 {
+                println!();
+            }
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            println!("------");
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let Some(solution) = solution else {
+            if is_text {
+                println!("Not solved.");
+            }
+            return;
+        };
 
-        if is_release {
-            args.push("--release");
-        }
+        let puzzle = solution.puzzle;
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
+        let input = match try_read_file("inputs", puzzle) {
+            Ok(input) => input,
+            Err(e) => {
+                if is_text {
+                    println!("No input.");
+                }
+                errors.push(if e.is_not_found() {
+                    Error::MissingInput {
+                        puzzle,
+                        path: e.path,
+                    }
+                } else {
+                    Error::Context {
+                        context: format!("failed to read input of day {day}"),
+                        source: Box::new(e.into()),
+                    }
+                });
+                return;
+            }
+        };
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        let output = solution.run(&input, format);
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        if format == OutputFormat::Ndjson {
+            output
+                .iter()
+                .for_each(|record| println!("{}", record.to_json()));
+        }
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        timings.push(collect_timings(&output, puzzle));
+        records.extend(output);
+    });
 
-        let mut output = vec![];
+    if format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&records).expect("part records are always serializable")
+        );
+    }
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+    if is_timed {
+        let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        if is_text {
+            println!(
+                "\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        if is_release {
+            match readme_benchmarks::update(year, timings) {
+                Ok(()) if is_text => println!("Successfully updated README with benchmarks."),
+                Ok(()) => eprintln!("Successfully updated README with benchmarks."),
+                Err(e) => {
+                    eprintln!("Failed to update readme with benchmarks: {e}");
+                }
+            }
 
-        Ok(output)
+            if let Err(e) = bench_history::append(year, &Run::new(records)) {
+                eprintln!("Failed to write benchmark history: {e}");
+            }
+        }
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    let count = errors.len();
+    match errors.into_iter().next() {
+        None => Ok(()),
+        Some(error) if count == 1 => Err(error),
+        Some(error) => Err(error).context(format!(
+            "{count} of the selected days have no readable input"
+        )),
+    }
+}
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+fn collect_timings(records: &[PartRecord], puzzle: PuzzleId) -> Timings {
+    let mut timings = Timings {
+        puzzle,
+        parse: None,
+        part_1: None,
+        part_2: None,
+        memory: None,
+        total_nanos: 0_f64,
+    };
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+    timings.memory = records
+        .iter()
+        .filter_map(|record| record.timing.allocs)
+        .map(|allocs| allocs.peak_bytes)
+        .max()
+        .map(format_bytes);
+
+    records
+        .iter()
+        .filter(|record| record.success && record.timing.samples > 1)
+        .for_each(|record| {
+            let median = record.timing.median;
+            let timing_str = format!("{median:.1?}");
+
+            match record.part {
+                PARSE_PART => timings.parse = Some(timing_str),
+                1 => timings.part_1 = Some(timing_str),
+                2 => timings.part_2 = Some(timing_str),
+                _ => {}
+            }
 
-                timings.total_nanos += nanos;
-            });
+            #[allow(clippy::cast_precision_loss)]
+            {
+                timings.total_nanos += median.as_nanos() as f64;
+            }
+        });
 
-        timings
-    }
+    timings
+}
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+/// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
+#[cfg(feature = "test_lib")]
+macro_rules! assert_approx_eq {
+    ($a:expr, $b:expr) => {{
+        let (a, b) = (&$a, &$b);
+        assert!(
+            (*a - *b).abs() < 1.0e-6,
+            "{} is not approximately equal to {}",
+            *a,
+            *b
+        );
+    }};
+}
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::collect_timings;
+
+    use crate::template::runner::{PartRecord, PARSE_PART};
+    use crate::{day, PuzzleId, Year};
+
+    const PUZZLE: PuzzleId = PuzzleId::new(Year::__new_unchecked(2023), day!(1));
+
+    #[test]
+    fn test_well_formed() {
+        let res = collect_timings(
+            &[
+                PartRecord::test(PUZZLE.day, 1, Some("0"), &[74, 74, 75]),
+                PartRecord::test(PUZZLE.day, 2, Some("10"), &[74_130_000, 74_130_000]),
+            ],
+            PUZZLE,
+        );
+        assert_approx_eq!(res.total_nanos, 74130074_f64);
+        assert_eq!(res.part_1.unwrap(), "74.0ns");
+        assert_eq!(res.part_2.unwrap(), "74.1ms");
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    #[test]
+    fn test_patterns_in_input() {
+        let line = PartRecord::test(
+            PUZZLE.day,
+            1,
+            Some("@ @ @ ( ) ms (2s @ 5 samples)"),
+            &[100, 100],
+        )
+        .to_json();
+        let res = collect_timings(&[PartRecord::from_json(&line).unwrap()], PUZZLE);
+        assert_approx_eq!(res.total_nanos, 100_f64);
+        assert_eq!(res.part_1.unwrap(), "100.0ns");
+        assert_eq!(res.part_2.is_none(), true);
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
+    #[test]
+    fn test_missing_parts() {
+        let res = collect_timings(
+            &[
+                PartRecord::test(PUZZLE.day, 1, None, &[10, 10]),
+                PartRecord::test(PUZZLE.day, 2, None, &[10, 10]),
+            ],
+            PUZZLE,
+        );
+        assert_approx_eq!(res.total_nanos, 0_f64);
+        assert_eq!(res.part_1.is_none(), true);
+        assert_eq!(res.part_2.is_none(), true);
+    }
 
-        #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+    #[test]
+    fn test_parse_step() {
+        let mut parse = PartRecord::test(PUZZLE.day, PARSE_PART, None, &[1_000, 1_000]);
+        parse.success = true;
+        let res = collect_timings(
+            &[parse, PartRecord::test(PUZZLE.day, 1, Some("1"), &[10, 10])],
+            PUZZLE,
+        );
+        assert_approx_eq!(res.total_nanos, 1010_f64);
+        assert_eq!(res.parse.unwrap(), "1.0µs");
+        assert_eq!(res.part_1.unwrap(), "10.0ns");
+    }
 
-        #[test]
-        fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+    #[test]
+    fn test_untimed_parts() {
+        let res = collect_timings(
+            &[PartRecord::test(PUZZLE.day, 1, Some("42"), &[10])],
+            PUZZLE,
+        );
+        assert_approx_eq!(res.total_nanos, 0_f64);
+        assert_eq!(res.part_1.is_none(), true);
     }
 }
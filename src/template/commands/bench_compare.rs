@@ -0,0 +1,154 @@
+use std::time::Duration;
+
+use crate::template::{
+    bench_history::{self, Run},
+    runner::{PartRecord, PARSE_PART},
+    Context, Error, ANSI_BOLD, ANSI_RESET,
+};
+use crate::{Day, Year};
+
+/// Change of a part's median time between two runs.
+#[derive(Debug, Clone, PartialEq)]
+struct Change {
+    day: Day,
+    part: u8,
+    baseline: Duration,
+    latest: Duration,
+}
+
+impl Change {
+    fn percent(&self) -> f64 {
+        let baseline = self.baseline.as_secs_f64();
+        (self.latest.as_secs_f64() - baseline) / baseline * 100.0
+    }
+}
+
+fn is_timed(record: &PartRecord) -> bool {
+    record.success && record.timing.samples > 1 && !record.timing.median.is_zero()
+}
+
+/// Pairs up the timed parts of both runs, parts that only ran in one of them are left out.
+fn compare(baseline: &Run, latest: &Run) -> Vec<Change> {
+    latest
+        .records
+        .iter()
+        .filter(|record| is_timed(record))
+        .filter_map(|record| {
+            let previous = baseline
+                .get(record.day, record.part)
+                .filter(|x| is_timed(x))?;
+            Some(Change {
+                day: record.day,
+                part: record.part,
+                baseline: previous.timing.median,
+                latest: record.timing.median,
+            })
+        })
+        .collect()
+}
+
+fn describe(run: &Run) -> &str {
+    run.commit.as_deref().unwrap_or("unknown commit")
+}
+
+/// Compares the latest `cargo time` run of `year` with a baseline, see [`bench_history::find_baseline`].
+/// Fails if any part got slower by more than `threshold` percent.
+pub fn handle(year: Year, baseline: Option<&str>, threshold: f64) -> Result<(), Error> {
+    let runs = bench_history::load(year).context("failed to read benchmark history")?;
+
+    let Some(latest) = runs.last() else {
+        return Err(Error::Failed(format!(
+            "no benchmark history for {year} yet, run `cargo time` first."
+        )));
+    };
+
+    let Some(previous) = bench_history::find_baseline(&runs, baseline) else {
+        return Err(Error::Failed(match baseline {
+            Some(baseline) => format!("no run of commit \"{baseline}\" found."),
+            None => "only one run recorded, run `cargo time` again to compare.".into(),
+        }));
+    };
+
+    println!(
+        "Comparing {} with baseline {}, threshold {threshold}%\n",
+        describe(latest),
+        describe(previous)
+    );
+    println!("{ANSI_BOLD}| Day | Part | Baseline | Latest | Change |{ANSI_RESET}");
+
+    let changes = compare(previous, latest);
+    let mut regressions = 0;
+
+    for change in &changes {
+        let percent = change.percent();
+        let is_regression = percent > threshold;
+        if is_regression {
+            regressions += 1;
+        }
+
+        let part = match change.part {
+            PARSE_PART => "parse".to_string(),
+            part => part.to_string(),
+        };
+
+        println!(
+            "|  {} | {part} | {:.1?} | {:.1?} | {percent:+.1}% |{}",
+            change.day,
+            change.baseline,
+            change.latest,
+            if is_regression { " regression" } else { "" }
+        );
+    }
+
+    if regressions > 0 {
+        println!();
+        return Err(Error::Failed(format!(
+            "{regressions} part(s) got slower by more than {threshold}%."
+        )));
+    }
+
+    println!("\nNo regressions above {threshold}%.");
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, Change};
+    use crate::day;
+    use crate::template::bench_history::Run;
+    use crate::template::runner::PartRecord;
+
+    fn run(records: Vec<PartRecord>) -> Run {
+        Run {
+            timestamp: 0,
+            commit: None,
+            records,
+        }
+    }
+
+    #[test]
+    fn compares_medians() {
+        let baseline = run(vec![
+            PartRecord::test(day!(1), 1, Some("1"), &[100, 100]),
+            PartRecord::test(day!(1), 2, Some("1"), &[200, 200]),
+            PartRecord::test(day!(2), 1, Some("1"), &[10, 10]),
+        ]);
+        let latest = run(vec![
+            PartRecord::test(day!(1), 1, Some("1"), &[150, 150]),
+            PartRecord::test(day!(1), 2, Some("1"), &[100, 100]),
+            PartRecord::test(day!(3), 1, Some("1"), &[10, 10]),
+        ]);
+
+        let changes = compare(&baseline, &latest);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].percent(), 50.0);
+        assert_eq!(changes[1].percent(), -50.0);
+    }
+
+    #[test]
+    fn ignores_untimed_parts() {
+        let baseline = run(vec![PartRecord::test(day!(1), 1, Some("1"), &[100])]);
+        let latest = run(vec![PartRecord::test(day!(1), 1, Some("1"), &[150, 150])]);
+        assert_eq!(compare(&baseline, &latest), Vec::<Change>::new());
+    }
+}
@@ -0,0 +1,169 @@
+use std::process::{Command, Stdio};
+
+use super::{check_status, year_from_args};
+use crate::template::{
+    get_data_path,
+    registry::Registry,
+    runner::{OutputFormat, PartRecord},
+    submissions::Ledger,
+    try_read_file, Context, Error, ANSI_BOLD, ANSI_RESET,
+};
+use crate::{all_days, Day, PuzzleId, Year};
+
+/// Builds the `all` binary and runs it in answer checking mode for `year`.
+pub fn handle(year: Year, is_release: bool) -> Result<(), Error> {
+    let mut args = vec!["run", "--quiet", "--bin", "all"];
+
+    if is_release {
+        args.push("--release");
+    }
+
+    let year = year.to_string();
+    args.extend(["--", "--check-answers", "--year", &year]);
+
+    let status = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .context("failed to run cargo")?;
+
+    check_status(&args, status)
+}
+
+/// Result of checking one part against its known-good answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Check {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// There is no known-good answer for this part.
+    Unknown,
+}
+
+impl Check {
+    fn new(expected: Option<&str>, record: Option<&PartRecord>) -> Self {
+        let Some(expected) = expected else {
+            return Check::Unknown;
+        };
+
+        let actual = record.and_then(|r| r.answer.as_deref());
+
+        if actual == Some(expected) {
+            Check::Pass
+        } else {
+            Check::Fail {
+                expected: expected.to_string(),
+                actual: actual.unwrap_or("✖").to_string(),
+            }
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            Check::Pass => "✔",
+            Check::Fail { .. } => "✖",
+            Check::Unknown => "?",
+        }
+    }
+}
+
+/// Entry point of the `all` binary with `--check-answers`.
+///
+/// Runs every registered day against its real input and compares the answers with the
+/// known-good answers in `data/<year>/answers/<day>.toml`. Fails with [`Error::WrongAnswer`] on mismatch.
+pub fn main(registry: &Registry) -> Result<(), Error> {
+    let year = year_from_args()?;
+
+    println!("{ANSI_BOLD}| Day | Part 1 | Part 2 |{ANSI_RESET}");
+
+    let mut failures: Vec<(Day, u8, String, String)> = vec![];
+
+    for day in all_days(year) {
+        let puzzle = PuzzleId::new(year, day);
+
+        let Some(solution) = registry.get(puzzle) else {
+            continue;
+        };
+
+        if !get_data_path("inputs", puzzle, "txt").exists() {
+            println!("|  {day} |   -    |   -    | missing input");
+            continue;
+        }
+
+        let ledger = Ledger::load(puzzle)
+            .with_context(|| format!("failed to read answers for day {day}"))?;
+        let input = try_read_file("inputs", puzzle)
+            .with_context(|| format!("failed to read input for day {day}"))?;
+        let records = solution.run(&input, OutputFormat::Json);
+
+        let checks: Vec<Check> = [1, 2]
+            .into_iter()
+            .map(|part| {
+                let record = records.iter().find(|r| r.part == part);
+                Check::new(ledger.correct_answer(part), record)
+            })
+            .collect();
+
+        println!(
+            "|  {day} |   {}    |   {}    |",
+            checks[0].symbol(),
+            checks[1].symbol()
+        );
+
+        for (part, check) in (1..).zip(checks) {
+            if let Check::Fail { expected, actual } = check {
+                failures.push((day, part, expected, actual));
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        println!("\nAll known answers match.");
+        return Ok(());
+    }
+
+    println!();
+    for (day, part, expected, actual) in &failures {
+        println!("Day {day} part {part}: expected {expected}, got {actual}");
+    }
+
+    Err(Error::WrongAnswer(format!(
+        "{} answer(s) differ from the known answers.",
+        failures.len()
+    )))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Check;
+    use crate::day;
+    use crate::template::runner::PartRecord;
+
+    #[test]
+    fn checks_answers() {
+        let record = |answer| PartRecord::test(day!(1), 1, answer, &[1]);
+
+        assert_eq!(
+            Check::new(Some("42"), Some(&record(Some("42")))),
+            Check::Pass
+        );
+        assert_eq!(Check::new(None, Some(&record(Some("42")))), Check::Unknown);
+        assert_eq!(
+            Check::new(Some("42"), Some(&record(Some("41")))),
+            Check::Fail {
+                expected: "42".into(),
+                actual: "41".into()
+            }
+        );
+        assert_eq!(
+            Check::new(Some("42"), Some(&record(None))),
+            Check::Fail {
+                expected: "42".into(),
+                actual: "✖".into()
+            }
+        );
+    }
+}
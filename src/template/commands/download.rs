@@ -1,15 +1,15 @@
 use crate::template::aoc_cli;
-use crate::Day;
-use std::process;
+use crate::template::commands::examples;
+use crate::template::{Context, Error};
+use crate::PuzzleId;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+pub fn handle(puzzle: PuzzleId) -> Result<(), Error> {
+    aoc_cli::check()?;
+    aoc_cli::download(puzzle).context("failed to reach adventofcode.com")?;
+
+    if let Err(e) = examples::update(puzzle) {
+        eprintln!("Failed to extract examples: {e}");
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    Ok(())
 }
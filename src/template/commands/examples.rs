@@ -0,0 +1,79 @@
+use std::{fs, io, path::PathBuf};
+
+use crate::template::examples::{self, Examples};
+use crate::template::{get_data_dir, get_data_path, Context, Error};
+use crate::PuzzleId;
+
+/// Returns the path of a solution relative to the project root, e.g. `src/bin/2023/01.rs`.
+/// The separators are always `/`, as the path is also written to `Cargo.toml` and the readme.
+#[must_use]
+pub fn get_module_path(puzzle: PuzzleId) -> PathBuf {
+    PathBuf::from(format!("src/bin/{}/{}.rs", puzzle.year, puzzle.day))
+}
+
+/// Writes the example inputs, existing files that are not empty are kept.
+fn write_inputs(puzzle: PuzzleId, examples: &Examples) -> io::Result<()> {
+    let dir = get_data_dir(puzzle.year).join("examples");
+    fs::create_dir_all(&dir)?;
+
+    for (i, input) in (1..).zip(&examples.inputs) {
+        let path = dir.join(format!("{}-{i}.txt", puzzle.day));
+        let is_empty = fs::read_to_string(&path).map_or(true, |s| s.trim().is_empty());
+
+        if is_empty {
+            fs::write(&path, format!("{input}\n"))?;
+            println!("Created example file \"{}\"", path.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Fills the scaffolded tests of the solution with the expected answers, if the solution exists.
+fn write_tests(puzzle: PuzzleId, examples: &Examples) -> io::Result<()> {
+    let path = get_module_path(puzzle);
+    let mut module = match fs::read_to_string(&path) {
+        Ok(module) => module,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+
+    if examples::fill_tests(&mut module, examples) > 0 {
+        fs::write(&path, module)?;
+        println!("Added expected answers to tests in \"{}\"", path.display());
+    }
+
+    Ok(())
+}
+
+/// Extracts the examples of a downloaded puzzle description, returns `false` if there is none.
+pub fn update(puzzle: PuzzleId) -> io::Result<bool> {
+    let markdown = match fs::read_to_string(get_data_path("puzzles", puzzle, "md")) {
+        Ok(markdown) => markdown,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    };
+
+    let examples = examples::extract(&markdown);
+    if examples.inputs.is_empty() {
+        println!("No examples found in the puzzle description.");
+    }
+
+    write_inputs(puzzle, &examples)?;
+    write_tests(puzzle, &examples)?;
+    Ok(true)
+}
+
+pub fn handle(puzzle: PuzzleId) -> Result<(), Error> {
+    let path = get_data_path("puzzles", puzzle, "md");
+
+    if update(puzzle).context("failed to extract examples")? {
+        Ok(())
+    } else {
+        Err(Error::Failed(format!(
+            "no puzzle description found at \"{}\", run `cargo download {}` first.",
+            path.display(),
+            puzzle.day
+        )))
+    }
+}
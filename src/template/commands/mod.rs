@@ -1,5 +1,91 @@
 pub mod all;
+pub mod bench_compare;
+pub mod check_answers;
 pub mod download;
+pub mod examples;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod stars;
+pub mod watch;
+
+use std::{env, process::ExitStatus};
+
+use crate::template::Error;
+use crate::Year;
+
+/// Reads the `--year` passed to the `all` binary, falls back to `AOC_YEAR`.
+fn year_from_args() -> Result<Year, Error> {
+    let args: Vec<String> = env::args().collect();
+
+    let year = args
+        .iter()
+        .position(|x| x == "--year")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|x| x.parse().ok())
+        .or_else(Year::from_env);
+
+    year.ok_or_else(|| {
+        Error::Usage(
+            "no year set, pass `--year` or set \"AOC_YEAR\" in \".cargo/config.toml\".".into(),
+        )
+    })
+}
+
+/// Passes on the exit status of a `cargo` child process as [`Error::Child`].
+fn check_status(args: &[impl AsRef<str>], status: ExitStatus) -> Result<(), Error> {
+    if status.success() {
+        return Ok(());
+    }
+
+    let args: Vec<&str> = args.iter().map(AsRef::as_ref).collect();
+    Err(Error::Child {
+        command: format!("cargo {}", args.join(" ")),
+        // terminated by a signal.
+        code: status.code().unwrap_or(1),
+    })
+}
+
+/// Benchmark settings that are forwarded to the solution binaries.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BenchArgs {
+    pub warmup: Option<u128>,
+    pub keep_outliers: bool,
+}
+
+impl BenchArgs {
+    /// Builds the command-line arguments understood by `runner::bench`.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(warmup) = self.warmup {
+            args.push("--warmup".into());
+            args.push(warmup.to_string());
+        }
+
+        if self.keep_outliers {
+            args.push("--keep-outliers".into());
+        }
+
+        args
+    }
+}
+
+/// Limits that are forwarded to the solution binaries.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LimitArgs {
+    /// Seconds after which a step of a solution is reported as timed out.
+    pub timeout: Option<f64>,
+}
+
+impl LimitArgs {
+    /// Builds the command-line arguments understood by the runner.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self.timeout {
+            Some(timeout) => vec!["--timeout".into(), timeout.to_string()],
+            None => vec![],
+        }
+    }
+}
@@ -1,18 +1,21 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
 };
 
-use crate::Day;
+use crate::template::commands::examples::{self, get_module_path};
+use crate::template::{get_data_path, Context, Error};
+use crate::{PuzzleId, Year};
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+/// Template that is used if `templates/default.rs` does not exist.
+pub(crate) const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!({{day}});
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Option<{{return_type}}> {
     None
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Option<{{return_type}}> {
     None
 }
 
@@ -34,61 +37,271 @@ mod tests {
 }
 "#;
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
+static TEMPLATE_DIR: &str = "templates";
+static DEFAULT_TEMPLATE: &str = "default";
+static DEFAULT_RETURN_TYPE: &str = "u32";
+
+/// Options of `cargo scaffold`.
+#[derive(Debug, Default, Clone)]
+pub struct ScaffoldArgs {
+    /// Name of a template in `templates/`, without the `.rs` extension.
+    pub template: Option<String>,
+    /// Return type of both parts, `u32` if not set.
+    pub return_type: Option<String>,
+    /// Print what would be created without touching any files.
+    pub dry_run: bool,
+    /// Overwrite an existing solution file.
+    pub force: bool,
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+/// Values that are substituted for the `{{name}}` placeholders of a template.
+struct Placeholders {
+    day: String,
+    year: String,
+    return_type: String,
+    title: String,
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+impl Placeholders {
+    fn new(puzzle: PuzzleId, return_type: &str) -> Self {
+        let title = fs::read_to_string(get_data_path("puzzles", puzzle, "md"))
+            .ok()
+            .and_then(|markdown| parse_title(&markdown))
+            .unwrap_or_else(|| format!("Day {}", puzzle.day.into_inner()));
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+        Self {
+            day: puzzle.day.into_inner().to_string(),
+            year: puzzle.year.to_string(),
+            return_type: return_type.to_string(),
+            title,
         }
-    };
+    }
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
+    fn render(&self, template: &str) -> String {
+        template
+            .replace("{{day}}", &self.day)
+            .replace("{{year}}", &self.year)
+            .replace("{{return_type}}", &self.return_type)
+            .replace("{{title}}", &self.title)
     }
+}
+
+/// Reads the title of a downloaded puzzle description, e.g. `Trebuchet?!` of `## --- Day 1: Trebuchet?! ---`.
+fn parse_title(markdown: &str) -> Option<String> {
+    let heading = markdown
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("## --- Day "))?;
+    let title = heading.split_once(": ")?.1.trim_end_matches("---").trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+/// Loads a template from `templates/<name>.rs`, falls back to [`MODULE_TEMPLATE`] for the default template.
+fn load_template(name: &str) -> Result<String, Error> {
+    let path = Path::new(TEMPLATE_DIR).join(format!("{name}.rs"));
+
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(e) if e.kind() == io::ErrorKind::NotFound && name == DEFAULT_TEMPLATE => {
+            Ok(MODULE_TEMPLATE.to_string())
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let available = list_templates();
+            Err(Error::Usage(if available.is_empty() {
+                format!("template \"{}\" not found.", path.display())
+            } else {
+                format!(
+                    "template \"{}\" not found, available templates: {}.",
+                    path.display(),
+                    available.join(", ")
+                )
+            }))
         }
+        Err(e) => Err(Error::io(path, e)).context("failed to read template"),
     }
+}
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+fn list_templates() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATE_DIR)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+        .collect();
+    names.sort();
+    names
+}
+
+fn create_module_file(path: &Path, force: bool) -> Result<File, std::io::Error> {
+    create_parent_dir(path)?;
+    if force {
+        File::create(path)
+    } else {
+        OpenOptions::new().write(true).create_new(true).open(path)
+    }
+}
+
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    create_parent_dir(path)?;
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+}
+
+fn create_parent_dir(path: &Path) -> Result<(), std::io::Error> {
+    match path.parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    }
+}
+
+fn is_bin_registered(puzzle: PuzzleId) -> Result<bool, std::io::Error> {
+    let manifest = fs::read_to_string("Cargo.toml")?;
+    Ok(manifest.contains(&format!("name = \"{}\"", puzzle.bin_name())))
+}
+
+/// Appends a `[[bin]]` entry for the puzzle to `Cargo.toml`, solutions in year directories are not discovered by cargo.
+fn register_bin(puzzle: PuzzleId) -> Result<(), std::io::Error> {
+    let mut manifest = OpenOptions::new().append(true).open("Cargo.toml")?;
+    write!(
+        manifest,
+        "\n[[bin]]\nname = \"{}\"\npath = \"{}\"\n",
+        puzzle.bin_name(),
+        get_module_path(puzzle).display()
+    )
+}
+
+fn print_dry_run(puzzle: PuzzleId, module_path: &Path, module: &str, data_paths: &[PathBuf]) {
+    let action = if module_path.exists() {
+        "overwrite"
+    } else {
+        "create"
+    };
+    println!("Would {action} module file \"{}\":", module_path.display());
+    println!("---");
+    print!("{module}");
+    println!("---");
+
+    if !is_bin_registered(puzzle).unwrap_or(false) {
+        println!(
+            "Would add binary \"{}\" to \"Cargo.toml\"",
+            puzzle.bin_name()
+        );
+    }
+
+    for path in data_paths.iter().filter(|path| !path.exists()) {
+        println!("Would create empty file \"{}\"", path.display());
+    }
+}
+
+pub fn handle(puzzle: PuzzleId, args: &ScaffoldArgs) -> Result<(), Error> {
+    let input_path = get_data_path("inputs", puzzle, "txt");
+    let example_path = get_data_path("examples", puzzle, "txt");
+    let module_path = get_module_path(puzzle);
+
+    let template = load_template(args.template.as_deref().unwrap_or(DEFAULT_TEMPLATE))?;
+
+    let placeholders = Placeholders::new(
+        puzzle,
+        args.return_type.as_deref().unwrap_or(DEFAULT_RETURN_TYPE),
+    );
+    let module = placeholders.render(&template);
+
+    if args.dry_run {
+        print_dry_run(puzzle, &module_path, &module, &[input_path, example_path]);
+        return Ok(());
+    }
+
+    let mut file = match create_module_file(&module_path, args.force) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            return Err(Error::Usage(format!(
+                "module file \"{}\" already exists, pass `--force` to overwrite it.",
+                module_path.display()
+            )));
         }
+        Err(e) => return Err(Error::io(&module_path, e)).context("failed to create module file"),
+    };
+
+    file.write_all(module.as_bytes())
+        .map_err(|e| Error::io(&module_path, e))
+        .context("failed to write module contents")?;
+    println!("Created module file \"{}\"", module_path.display());
+
+    if !is_bin_registered(puzzle).map_err(|e| Error::io("Cargo.toml", e))? {
+        register_bin(puzzle)
+            .map_err(|e| Error::io("Cargo.toml", e))
+            .context("failed to add binary")?;
+        println!("Added binary \"{}\" to \"Cargo.toml\"", puzzle.bin_name());
+    }
+
+    create_file(&input_path)
+        .map_err(|e| Error::io(&input_path, e))
+        .context("failed to create input file")?;
+    println!("Created empty input file \"{}\"", input_path.display());
+
+    create_file(&example_path)
+        .map_err(|e| Error::io(&example_path, e))
+        .context("failed to create example file")?;
+    println!("Created empty example file \"{}\"", example_path.display());
+
+    // the puzzle may have been downloaded before the day was scaffolded.
+    if let Err(e) = examples::update(puzzle) {
+        eprintln!("Failed to extract examples: {e}");
     }
 
+    let year_arg = if Year::from_env() == Some(puzzle.year) {
+        String::new()
+    } else {
+        format!(" --year {}", puzzle.year)
+    };
+
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    println!(
+        "🎄 Type `cargo solve {}{year_arg}` to run your solution.",
+        puzzle.day
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_title, Placeholders, MODULE_TEMPLATE};
+
+    fn placeholders() -> Placeholders {
+        Placeholders {
+            day: "7".into(),
+            year: "2023".into(),
+            return_type: "u64".into(),
+            title: "Camel Cards".into(),
+        }
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let rendered = placeholders()
+            .render("// {{year}} day {{day}}: {{title}}\nfn f() -> {{return_type}} {}");
+        assert_eq!(rendered, "// 2023 day 7: Camel Cards\nfn f() -> u64 {}");
+    }
+
+    #[test]
+    fn renders_default_template() {
+        let rendered = placeholders().render(MODULE_TEMPLATE);
+        assert!(rendered.starts_with("advent_of_code::solution!(7);"));
+        assert!(rendered.contains("pub fn part_two(input: &str) -> Option<u64> {"));
+        assert!(!rendered.contains("{{"));
+    }
+
+    #[test]
+    fn parses_titles() {
+        assert_eq!(
+            parse_title("## --- Day 1: Trebuchet?! ---\n\nSomething is wrong.").as_deref(),
+            Some("Trebuchet?!")
+        );
+        assert_eq!(parse_title("no heading"), None);
+    }
 }
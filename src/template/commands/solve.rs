@@ -1,14 +1,61 @@
+use std::io::{BufRead, BufReader};
 use std::process::{Command, Stdio};
 
-use crate::Day;
+use super::check_status;
+use crate::template::alloc_stats;
+use crate::template::commands::{BenchArgs, LimitArgs};
+use crate::template::runner::{OutputFormat, PartRecord};
+use crate::template::{Context, Error};
+use crate::PuzzleId;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Input overrides that are forwarded to the solution binary, see [`InputSource`](crate::template::runner::InputSource).
+#[derive(Debug, Default, Clone)]
+pub struct InputArgs {
+    pub input: Option<String>,
+    pub example: Option<u8>,
+}
+
+impl InputArgs {
+    fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(input) = &self.input {
+            args.push("--input".into());
+            args.push(input.clone());
+        }
+
+        if let Some(example) = self.example {
+            args.push("--example".into());
+            args.push(example.to_string());
+        }
+
+        args
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    time: bool,
+    submit_part: Option<u8>,
+    bench: &BenchArgs,
+    format: OutputFormat,
+    input: &InputArgs,
+    limits: LimitArgs,
+) -> Result<(), Error> {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if release {
         cmd_args.push("--release".to_string());
     }
 
+    // the solution binary counts allocations if the command was built with them.
+    if alloc_stats::is_enabled() {
+        cmd_args.push("--features".to_string());
+        cmd_args.push("alloc_stats".to_string());
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -18,14 +65,48 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
 
     if time {
         cmd_args.push("--time".to_string());
+        cmd_args.extend(bench.to_args());
+    }
+
+    if format != OutputFormat::Text {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.as_str().to_string());
     }
 
+    cmd_args.extend(input.to_args());
+    cmd_args.extend(limits.to_args());
+
+    // the solution binary emits one record per line, collect them into a single document for `json`.
+    let stdout = if format == OutputFormat::Json {
+        Stdio::piped()
+    } else {
+        Stdio::inherit()
+    };
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
-        .stdout(Stdio::inherit())
+        .stdout(stdout)
         .stderr(Stdio::inherit())
         .spawn()
-        .unwrap();
+        .context("failed to run cargo")?;
+
+    if let Some(stdout) = cmd.stdout.take() {
+        let mut records = vec![];
+
+        for line in BufReader::new(stdout).lines() {
+            let line = line?;
+            match PartRecord::from_json(&line) {
+                Some(record) => records.push(record),
+                None => eprintln!("{line}"),
+            }
+        }
+
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&records).expect("part records are always serializable")
+        );
+    }
 
-    cmd.wait().unwrap();
+    let status = cmd.wait()?;
+    check_status(&cmd_args, status)
 }
@@ -0,0 +1,24 @@
+use std::{env, fs, path::Path};
+
+use crate::template::{readme_stars, Context, Error};
+use crate::Year;
+
+/// Fills the stars table of the readme, either from the submission ledgers of `year`
+/// or from a saved private leaderboard.
+pub fn handle(year: Year, leaderboard: Option<&Path>, user_id: Option<&str>) -> Result<(), Error> {
+    let stars = match leaderboard {
+        Some(path) => {
+            let json = fs::read_to_string(path).map_err(|e| Error::io(path, e))?;
+            let user_id = user_id
+                .map(ToString::to_string)
+                .or_else(|| env::var("AOC_USER_ID").ok());
+            readme_stars::from_leaderboard(&json, user_id.as_deref())
+                .with_context(|| format!("failed to read leaderboard \"{}\"", path.display()))?
+        }
+        None => readme_stars::from_ledgers(year)?,
+    };
+
+    readme_stars::update(year, &stars).context("failed to update readme with stars")?;
+    println!("Successfully updated README with stars.");
+    Ok(())
+}
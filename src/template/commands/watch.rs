@@ -0,0 +1,187 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use super::examples::get_module_path;
+use crate::template::{
+    find_data_root, get_data_dir, get_data_path, Error, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::PuzzleId;
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+static ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// Modification times of the watched files, [`None`] for files that do not exist.
+type Snapshot = Vec<(PathBuf, Option<SystemTime>)>;
+
+/// Returns the files that affect the result of a day: its module, the library and template sources, its input
+/// and its examples. Source paths are resolved against the same root as the data paths.
+/// `input` is an additional input file passed with `--input`.
+fn watched_paths(puzzle: PuzzleId, input: Option<&Path>) -> Vec<PathBuf> {
+    let root = find_data_root();
+    let mut paths = vec![root.join(get_module_path(puzzle))];
+
+    // the other days' solutions do not affect this day.
+    let src = root.join("src");
+    let mut sources = vec![];
+    find_sources(&src, &src.join("bin"), &mut sources);
+    sources.sort();
+    paths.extend(sources);
+
+    paths.push(get_data_path("inputs", puzzle, "txt"));
+    paths.push(get_data_path("examples", puzzle, "txt"));
+
+    // numbered examples, e.g. `01-2.txt`, may be added while watching.
+    let prefix = format!("{}-", puzzle.day);
+    let mut examples: Vec<PathBuf> = fs::read_dir(get_data_dir(puzzle.year).join("examples"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".txt"))
+        })
+        .collect();
+    examples.sort();
+    paths.extend(examples);
+
+    paths.extend(input.map(Path::to_path_buf));
+    paths
+}
+
+/// Collects every `*.rs` file below `dir`, except for those below `skip`.
+fn find_sources(dir: &Path, skip: &Path, sources: &mut Vec<PathBuf>) {
+    for path in fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+    {
+        if path.is_dir() {
+            if path != skip {
+                find_sources(&path, skip, sources);
+            }
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            sources.push(path);
+        }
+    }
+}
+
+fn snapshot(paths: &[PathBuf]) -> Snapshot {
+    paths
+        .iter()
+        .map(|path| {
+            let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+            (path.clone(), modified)
+        })
+        .collect()
+}
+
+/// Runs the tests of the day, failures are printed by `cargo test` itself.
+fn run_tests(puzzle: PuzzleId) {
+    println!("{ANSI_BOLD}Tests{ANSI_RESET}");
+
+    let status = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &puzzle.bin_name()])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(_) => println!("{ANSI_ITALIC}Tests failed.{ANSI_RESET}"),
+        Err(e) => eprintln!("Error: failed to run cargo: {e}"),
+    }
+}
+
+/// Re-runs the tests and `solve` whenever the module, the sources in `src/`, the input or the examples of the day change.
+/// `solve` is usually [`solve::handle`](super::solve::handle) with the options of the command-line.
+/// Errors of a run are printed and do not stop watching, press Ctrl-C to stop.
+pub fn handle(
+    puzzle: PuzzleId,
+    input: Option<&Path>,
+    solve: impl Fn() -> Result<(), Error>,
+) -> Result<(), Error> {
+    let mut last: Option<Snapshot> = None;
+
+    loop {
+        let current = snapshot(&watched_paths(puzzle, input));
+
+        if last.as_ref() != Some(&current) {
+            print!("{ANSI_CLEAR}");
+            println!(
+                "{ANSI_ITALIC}Watching day {} of {}, press Ctrl-C to stop.{ANSI_RESET}\n",
+                puzzle.day, puzzle.year
+            );
+
+            run_tests(puzzle);
+            println!("\n{ANSI_BOLD}Solution{ANSI_RESET}");
+
+            if let Err(e) = solve() {
+                eprintln!("Error: {e}");
+            }
+
+            // changes made while running are picked up by the next poll.
+            last = Some(current);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{snapshot, watched_paths};
+    use crate::{day, PuzzleId, Year};
+    use std::fs::{self, File};
+    use std::path::{Path, PathBuf};
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn watches_day_files() {
+        let puzzle = PuzzleId::new(Year::__new_unchecked(2023), day!(1));
+        let paths = watched_paths(puzzle, Some(Path::new("big.txt")));
+
+        assert!(paths[0].is_absolute());
+        assert!(paths[0].ends_with("src/bin/2023/01.rs"));
+        for source in ["src/lib.rs", "src/intervals.rs", "src/template/runner.rs"] {
+            assert!(paths.iter().any(|path| path.ends_with(source)), "{source}");
+        }
+        assert!(!paths
+            .iter()
+            .any(|path| path.ends_with("src/bin/2023/02.rs") || path.ends_with("src/bin/all.rs")));
+        assert!(paths
+            .iter()
+            .any(|path| path.ends_with("data/2023/inputs/01.txt")));
+        assert_eq!(paths.last(), Some(&PathBuf::from("big.txt")));
+    }
+
+    #[test]
+    fn detects_modifications() {
+        let path = std::env::temp_dir().join(format!("aoc-watch-{}.txt", std::process::id()));
+        let paths = vec![path.clone()];
+
+        let missing = snapshot(&paths);
+        assert_eq!(missing[0].1, None);
+
+        let file = File::create(&path).unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(1))
+            .unwrap();
+        let created = snapshot(&paths);
+        assert_ne!(created, missing);
+
+        file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(2))
+            .unwrap();
+        assert_ne!(snapshot(&paths), created);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
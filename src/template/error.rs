@@ -0,0 +1,231 @@
+//! Error type of the template commands, see [`Error::exit_code`] for how errors map to exit codes.
+
+use std::{fmt::Display, io, path::PathBuf, process};
+
+use crate::template::aoc_cli::AocCommandError;
+use crate::template::ReadFileError;
+use crate::{DayFromStrError, PuzzleId, YearFromStrError};
+
+/// Exit code of any error that has no more specific code.
+pub const EXIT_FAILURE: i32 = 1;
+/// Exit code of invalid command-line arguments.
+pub const EXIT_USAGE: i32 = 2;
+/// Exit code of a missing puzzle input.
+pub const EXIT_MISSING_INPUT: i32 = 3;
+/// Exit code of failed requests to the Advent of Code website.
+pub const EXIT_NETWORK: i32 = 4;
+/// Exit code of answers that do not match the known answer.
+pub const EXIT_WRONG_ANSWER: i32 = 5;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Invalid command-line arguments.
+    Usage(String),
+    /// The input of a puzzle does not exist yet.
+    MissingInput { puzzle: PuzzleId, path: PathBuf },
+    /// A request to the Advent of Code website failed.
+    Aoc(AocCommandError),
+    /// Reading or writing a file failed.
+    Io {
+        path: Option<PathBuf>,
+        source: io::Error,
+    },
+    /// A file or response has an unexpected format.
+    Parse(String),
+    /// Answers differ from the known answers.
+    WrongAnswer(String),
+    /// A command failed for another reason, e.g. a benchmark regression.
+    Failed(String),
+    /// A child process exited with a non-zero status, which is passed on.
+    Child { command: String, code: i32 },
+    /// Describes what was being done when `source` occurred.
+    Context { context: String, source: Box<Error> },
+}
+
+impl Error {
+    /// Returns the exit code for this error, errors with context use the code of their source.
+    #[must_use]
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Usage(_) => EXIT_USAGE,
+            Error::MissingInput { .. } => EXIT_MISSING_INPUT,
+            Error::Aoc(AocCommandError::IoError) => EXIT_FAILURE,
+            Error::Aoc(_) => EXIT_NETWORK,
+            Error::WrongAnswer(_) => EXIT_WRONG_ANSWER,
+            Error::Child { code, .. } => *code,
+            Error::Context { source, .. } => source.exit_code(),
+            Error::Io { .. } | Error::Parse(_) | Error::Failed(_) => EXIT_FAILURE,
+        }
+    }
+
+    /// Prints the error and exits with its [exit code](Error::exit_code).
+    pub fn exit(&self) -> ! {
+        eprintln!("Error: {self}");
+        process::exit(self.exit_code());
+    }
+
+    /// Wraps an [`io::Error`] that occurred while accessing `path`.
+    pub fn io(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        Error::Io {
+            path: Some(path.into()),
+            source,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(message)
+            | Error::Parse(message)
+            | Error::WrongAnswer(message)
+            | Error::Failed(message) => write!(f, "{message}"),
+            Error::MissingInput { puzzle, path } => {
+                let year_arg = if crate::Year::from_env() == Some(puzzle.year) {
+                    String::new()
+                } else {
+                    format!(" --year {}", puzzle.year)
+                };
+                write!(
+                    f,
+                    "no input found at \"{}\", run `cargo download {}{year_arg}` to fetch it.",
+                    path.display(),
+                    puzzle.day
+                )
+            }
+            Error::Aoc(e) => write!(f, "{e}"),
+            Error::Io {
+                path: Some(path),
+                source,
+            } => write!(f, "\"{}\": {source}", path.display()),
+            Error::Io { path: None, source } => write!(f, "{source}"),
+            Error::Child { command, code } => write!(f, "`{command}` exited with status {code}."),
+            Error::Context { context, source } => write!(f, "{context}: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Context { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Self {
+        Error::Io { path: None, source }
+    }
+}
+
+impl From<ReadFileError> for Error {
+    fn from(e: ReadFileError) -> Self {
+        Error::io(e.path, e.source)
+    }
+}
+
+impl From<AocCommandError> for Error {
+    fn from(e: AocCommandError) -> Self {
+        Error::Aoc(e)
+    }
+}
+
+impl From<pico_args::Error> for Error {
+    fn from(e: pico_args::Error) -> Self {
+        Error::Usage(e.to_string())
+    }
+}
+
+impl From<DayFromStrError> for Error {
+    fn from(e: DayFromStrError) -> Self {
+        Error::Usage(e.to_string())
+    }
+}
+
+impl From<YearFromStrError> for Error {
+    fn from(e: YearFromStrError) -> Self {
+        Error::Usage(e.to_string())
+    }
+}
+
+/// Adds context to the error of a [`Result`], e.g. the day or file that was being processed.
+pub trait Context<T> {
+    fn context(self, context: impl Into<String>) -> Result<T, Error>;
+
+    fn with_context(self, context: impl FnOnce() -> String) -> Result<T, Error>;
+}
+
+impl<T, E: Into<Error>> Context<T> for Result<T, E> {
+    fn context(self, context: impl Into<String>) -> Result<T, Error> {
+        self.with_context(|| context.into())
+    }
+
+    fn with_context(self, context: impl FnOnce() -> String) -> Result<T, Error> {
+        self.map_err(|e| Error::Context {
+            context: context(),
+            source: Box::new(e.into()),
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Context, Error, EXIT_FAILURE, EXIT_MISSING_INPUT, EXIT_NETWORK, EXIT_USAGE};
+    use crate::template::aoc_cli::AocCommandError;
+    use crate::{day, PuzzleId, Year};
+    use std::error::Error as _;
+    use std::io;
+
+    #[test]
+    fn maps_exit_codes() {
+        let puzzle = PuzzleId::new(Year::__new_unchecked(2023), day!(1));
+        let missing = Error::MissingInput {
+            puzzle,
+            path: "data/2023/inputs/01.txt".into(),
+        };
+
+        assert_eq!(Error::Usage("x".into()).exit_code(), EXIT_USAGE);
+        assert_eq!(missing.exit_code(), EXIT_MISSING_INPUT);
+        assert_eq!(
+            Error::Aoc(AocCommandError::RateLimited).exit_code(),
+            EXIT_NETWORK
+        );
+        assert_eq!(
+            Error::Aoc(AocCommandError::IoError).exit_code(),
+            EXIT_FAILURE
+        );
+        assert_eq!(
+            Error::Child {
+                command: "cargo run".into(),
+                code: 42
+            }
+            .exit_code(),
+            42
+        );
+    }
+
+    #[test]
+    fn chains_context() {
+        let result: Result<(), io::Error> = Err(io::Error::new(io::ErrorKind::Other, "disk full"));
+        let e = result
+            .context("failed to write answers")
+            .with_context(|| "day 01".to_string())
+            .unwrap_err();
+
+        assert_eq!(e.to_string(), "day 01: failed to write answers: disk full");
+        assert_eq!(e.exit_code(), EXIT_FAILURE);
+        assert_eq!(
+            e.source().unwrap().to_string(),
+            "failed to write answers: disk full"
+        );
+    }
+
+    #[test]
+    fn context_keeps_exit_code() {
+        let result: Result<(), Error> = Err(Error::Usage("unknown command".into()));
+        assert_eq!(result.context("main").unwrap_err().exit_code(), EXIT_USAGE);
+    }
+}
@@ -0,0 +1,363 @@
+//! Extracts example inputs and their expected answers from a downloaded puzzle description.
+//! Works on the markdown written by `cargo download`, fenced code blocks and raw `<pre><code>` blocks are both recognized.
+//! The results are heuristics: the example of a part is the first code block introduced as an example,
+//! its answer is the last emphasized code span of the part.
+
+static PART_TWO_HEADING: &str = "--- Part Two ---";
+
+/// Expected answer of a part for one of the extracted examples.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedAnswer {
+    /// Number of the example, i.e. `1` for `<day>-1.txt`.
+    pub example: u8,
+    pub answer: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Examples {
+    /// Distinct example inputs, written to `<day>-1.txt`, `<day>-2.txt` and so on.
+    pub inputs: Vec<String>,
+    /// Expected answers of part 1 and part 2.
+    pub answers: [Option<ExpectedAnswer>; 2],
+}
+
+enum Block {
+    Text(String),
+    Code(String),
+}
+
+/// Extracts the examples of both parts from a puzzle description.
+/// Part 2 reuses the example of part 1 if it does not introduce a new one.
+#[must_use]
+pub fn extract(markdown: &str) -> Examples {
+    let (part_1, part_2) = match markdown.find(PART_TWO_HEADING) {
+        Some(i) => (&markdown[..i], Some(&markdown[i..])),
+        None => (markdown, None),
+    };
+
+    let mut examples = Examples::default();
+    let mut previous_example = None;
+
+    for (i, section) in [Some(part_1), part_2].into_iter().enumerate() {
+        let Some(section) = section else { continue };
+        let blocks = parse_blocks(section);
+
+        let Some(input) = find_example(&blocks).or(previous_example.take()) else {
+            continue;
+        };
+
+        let index = match examples.inputs.iter().position(|x| *x == input) {
+            Some(index) => index,
+            None => {
+                examples.inputs.push(input.clone());
+                examples.inputs.len() - 1
+            }
+        };
+
+        examples.answers[i] = find_answer(&blocks).map(|answer| ExpectedAnswer {
+            example: u8::try_from(index + 1).unwrap_or(u8::MAX),
+            answer,
+        });
+
+        previous_example = Some(input);
+    }
+
+    examples
+}
+
+/// Splits markdown into text and code blocks.
+fn parse_blocks(markdown: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut rest = markdown;
+
+    loop {
+        let fence = find_fence(rest);
+        let pre = rest.find("<pre><code>");
+
+        let (start, is_fence) = match (fence, pre) {
+            (Some(fence), Some(pre)) if pre < fence => (pre, false),
+            (Some(fence), _) => (fence, true),
+            (None, Some(pre)) => (pre, false),
+            (None, None) => {
+                blocks.push(Block::Text(rest.to_string()));
+                return blocks;
+            }
+        };
+
+        blocks.push(Block::Text(rest[..start].to_string()));
+
+        let (code, after) = if is_fence {
+            let body = rest[start..]
+                .find('\n')
+                .map_or("", |i| &rest[start + i + 1..]);
+            match find_fence(body) {
+                Some(end) => {
+                    let after = body[end..].find('\n').map_or("", |i| &body[end + i + 1..]);
+                    (body[..end].to_string(), after)
+                }
+                None => (body.to_string(), ""),
+            }
+        } else {
+            let body = &rest[start + "<pre><code>".len()..];
+            match body.find("</code></pre>") {
+                Some(end) => (
+                    decode_html(&body[..end]),
+                    &body[end + "</code></pre>".len()..],
+                ),
+                None => (decode_html(body), ""),
+            }
+        };
+
+        blocks.push(Block::Code(code.trim_end_matches('\n').to_string()));
+        rest = after;
+    }
+}
+
+/// Finds a code fence at the start of a line.
+fn find_fence(s: &str) -> Option<usize> {
+    if s.starts_with("```") {
+        return Some(0);
+    }
+    s.find("\n```").map(|i| i + 1)
+}
+
+/// Strips tags like `<em>` from the contents of a `<pre>` block and decodes entities.
+fn decode_html(s: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in s.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Returns the first code block that is introduced as an example, or the first code block.
+fn find_example(blocks: &[Block]) -> Option<String> {
+    let mut preceding = "";
+    let mut first = None;
+
+    for block in blocks {
+        match block {
+            Block::Text(text) => preceding = text,
+            Block::Code(code) if !code.trim().is_empty() => {
+                if preceding.to_lowercase().contains("example") {
+                    return Some(code.clone());
+                }
+                first.get_or_insert(code);
+            }
+            Block::Code(_) => {}
+        }
+    }
+
+    first.cloned()
+}
+
+/// Returns the last emphasized code span of the text, which is where puzzles state the expected result.
+fn find_answer(blocks: &[Block]) -> Option<String> {
+    let patterns = [("*`", "`*"), ("<code><em>", "</em></code>")];
+
+    blocks
+        .iter()
+        .filter_map(|block| match block {
+            Block::Text(text) => Some(text),
+            Block::Code(_) => None,
+        })
+        .flat_map(|text| {
+            patterns.iter().flat_map(move |(open, close)| {
+                text.match_indices(open).filter_map(move |(start, _)| {
+                    let value = &text[start + open.len()..];
+                    let end = value.find(close)?;
+                    Some((start, value[..end].trim().to_string()))
+                })
+            })
+        })
+        .filter(|(_, value)| !value.is_empty() && !value.contains('\n'))
+        .max_by_key(|(start, _)| *start)
+        .map(|(_, value)| value)
+}
+
+/// The test of a part as written by `cargo scaffold`.
+#[must_use]
+pub fn template_test(part: u8) -> String {
+    render_test(part, "read_file(\"examples\", DAY)".into(), "None".into())
+}
+
+/// A test of a part that checks the expected answer of its example.
+#[must_use]
+pub fn expected_test(part: u8, expected: &ExpectedAnswer) -> String {
+    let value = if expected.answer.parse::<i64>().is_ok() {
+        format!("Some({})", expected.answer)
+    } else {
+        format!("Some(String::from({:?}))", expected.answer)
+    };
+
+    render_test(
+        part,
+        format!("read_example(\"examples\", DAY, {})", expected.example),
+        value,
+    )
+}
+
+fn render_test(part: u8, read: String, value: String) -> String {
+    let name = if part == 1 { "one" } else { "two" };
+    [
+        "    #[test]".to_string(),
+        format!("    fn test_part_{name}() {{"),
+        format!("        let result = part_{name}(&advent_of_code::template::{read});"),
+        format!("        assert_eq!(result, {value});"),
+        "    }".to_string(),
+    ]
+    .join("\n")
+}
+
+/// Replaces the scaffolded tests of a module with tests of the expected answers.
+/// Tests that were already changed are kept, returns the number of replaced tests.
+pub fn fill_tests(module: &mut String, examples: &Examples) -> usize {
+    let mut replaced = 0;
+
+    for (part, expected) in (1..).zip(&examples.answers) {
+        let Some(expected) = expected else { continue };
+        let template = template_test(part);
+        if module.contains(&template) {
+            *module = module.replace(&template, &expected_test(part, expected));
+            replaced += 1;
+        }
+    }
+
+    replaced
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract, fill_tests, template_test, Examples, ExpectedAnswer};
+    use crate::template::commands::scaffold::MODULE_TEMPLATE;
+
+    const PUZZLE: &str = "## --- Day 1: Trebuchet?! ---
+
+The newly-improved calibration document consists of lines of text.
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the calibration values are `12` and `38`. Adding these together produces *`50`*.
+
+Your puzzle answer was `54331`.
+
+## --- Part Two ---
+
+Your calculation isn't quite right.
+
+Equipped with this new information, you now need to find the real first and last digit on each line. For example:
+
+```
+two1nine
+eightwothree
+```
+
+Adding these together produces *`112`*.
+";
+
+    #[test]
+    fn extracts_examples_of_both_parts() {
+        let examples = extract(PUZZLE);
+        assert_eq!(
+            examples.inputs,
+            vec!["1abc2\npqr3stu8vwx", "two1nine\neightwothree"]
+        );
+        assert_eq!(
+            examples.answers,
+            [
+                Some(ExpectedAnswer {
+                    example: 1,
+                    answer: "50".into()
+                }),
+                Some(ExpectedAnswer {
+                    example: 2,
+                    answer: "112".into()
+                })
+            ]
+        );
+    }
+
+    #[test]
+    fn reuses_example_of_part_one() {
+        let puzzle = "For example:\n\n```\n1 2\n```\n\nThe sum is *`3`*.\n\n## --- Part Two ---\n\nNow the product is *`2`*.\n";
+        let examples = extract(puzzle);
+        assert_eq!(examples.inputs, vec!["1 2"]);
+        assert_eq!(examples.answers[1].as_ref().unwrap().example, 1);
+        assert_eq!(examples.answers[1].as_ref().unwrap().answer, "2");
+    }
+
+    #[test]
+    fn extracts_html_blocks() {
+        let puzzle = "<p>For example:</p>\n<pre><code>a &lt; b\n<em>c</em>\n</code></pre>\n<p>The answer is <code><em>CMZ</em></code>.</p>";
+        let examples = extract(puzzle);
+        assert_eq!(examples.inputs, vec!["a < b\nc"]);
+        assert_eq!(examples.answers[0].as_ref().unwrap().answer, "CMZ");
+        assert_eq!(examples.answers[1], None);
+    }
+
+    #[test]
+    fn ignores_puzzles_without_examples() {
+        assert_eq!(
+            extract("## --- Day 1 ---\n\nNo code here.\n"),
+            Examples::default()
+        );
+    }
+
+    #[test]
+    fn fills_scaffolded_tests() {
+        let mut module = format!(
+            "mod tests {{\n{}\n\n{}\n}}",
+            template_test(1),
+            template_test(2)
+        );
+        let replaced = fill_tests(&mut module, &extract(PUZZLE));
+        assert_eq!(replaced, 2);
+        assert!(module.contains(
+            "        let result = part_one(&advent_of_code::template::read_example(\"examples\", DAY, 1));\n        assert_eq!(result, Some(50));"
+        ));
+        assert!(module.contains("assert_eq!(result, Some(112));"));
+
+        // tests that were already changed are kept.
+        assert_eq!(fill_tests(&mut module, &extract(PUZZLE)), 0);
+    }
+
+    #[test]
+    fn matches_scaffold_template() {
+        assert!(MODULE_TEMPLATE.contains(&template_test(1)));
+        assert!(MODULE_TEMPLATE.contains(&template_test(2)));
+    }
+
+    #[test]
+    fn quotes_non_numeric_answers() {
+        let mut module = template_test(1);
+        let examples = Examples {
+            inputs: vec!["x".into()],
+            answers: [
+                Some(ExpectedAnswer {
+                    example: 1,
+                    answer: "CMZ".into(),
+                }),
+                None,
+            ],
+        };
+        fill_tests(&mut module, &examples);
+        assert!(module.contains("assert_eq!(result, Some(String::from(\"CMZ\")));"));
+    }
+}
@@ -1,47 +1,216 @@
-use crate::Day;
-use std::{env, fs};
+use crate::{PuzzleId, Year};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
 
+pub mod alloc_stats;
 pub mod aoc_cli;
+pub mod aoc_client;
+pub mod bench_history;
+pub mod benchmark_chart;
 pub mod commands;
+pub mod error;
+pub mod examples;
 pub mod readme_benchmarks;
+pub mod readme_stars;
+pub mod registry;
 pub mod runner;
+pub mod submissions;
+
+pub use error::{Context, Error};
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Returns the data directory of a year, e.g. `data/2023`, below the root found by [`find_data_root`].
+/// Every read and write of data files goes through this, so they agree on the directory.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn get_data_dir(year: Year) -> PathBuf {
+    find_data_root().join("data").join(year.to_string())
 }
 
+/// Returns the path of a puzzle's file in a data folder, e.g. `data/2023/inputs/01.txt`.
 #[must_use]
-pub fn read_example(folder: &str, day: Day, example: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
+pub fn get_data_path(folder: &str, puzzle: PuzzleId, extension: &str) -> PathBuf {
+    get_data_dir(puzzle.year)
         .join(folder)
-        .join(format!("{day}-{example}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+        .join(format!("{}.{extension}", puzzle.day))
+}
+
+/// Error returned when a data file cannot be read, includes the path that was tried.
+#[derive(Debug)]
+pub struct ReadFileError {
+    pub path: PathBuf,
+    pub source: io::Error,
+}
+
+impl ReadFileError {
+    /// Returns `true` if the file does not exist, e.g. because the input was not downloaded yet.
+    #[must_use]
+    pub fn is_not_found(&self) -> bool {
+        self.source.kind() == io::ErrorKind::NotFound
+    }
+}
+
+impl Display for ReadFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "could not read \"{}\": {}",
+            self.path.display(),
+            self.source
+        )
+    }
+}
+
+impl std::error::Error for ReadFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Finds the directory that contains `data/` by walking up from `CARGO_MANIFEST_DIR`,
+/// so tests and binaries also find their inputs when they are not run from the crate root.
+/// Starts at the working directory if cargo did not set the variable, and returns the start
+/// if no `data/` exists yet, e.g. before the first download.
+fn find_data_root() -> PathBuf {
+    let start = env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .or_else(|| env::current_dir().ok());
+
+    start
+        .map(|dir| {
+            dir.ancestors()
+                .find(|dir| dir.join("data").is_dir())
+                .map_or_else(|| dir.clone(), Path::to_path_buf)
+        })
+        .unwrap_or_default()
+}
+
+fn try_read(path: PathBuf) -> Result<String, ReadFileError> {
+    fs::read_to_string(&path).map_err(|source| ReadFileError { path, source })
+}
+
+/// Reads a puzzle's text file from a data folder, e.g. `data/2023/inputs/01.txt`.
+pub fn try_read_file(folder: &str, puzzle: PuzzleId) -> Result<String, ReadFileError> {
+    try_read(get_data_path(folder, puzzle, "txt"))
+}
+
+/// Reads one of several text files of a puzzle, e.g. `data/2023/examples/01-2.txt`.
+pub fn try_read_example(
+    folder: &str,
+    puzzle: PuzzleId,
+    example: u8,
+) -> Result<String, ReadFileError> {
+    try_read(
+        get_data_dir(puzzle.year)
+            .join(folder)
+            .join(format!("{}-{example}.txt", puzzle.day)),
+    )
+}
+
+/// Helper function that reads a text file to a string.
+///
+/// # Panics
+/// Panics with the path that was tried if the file cannot be read, see [`try_read_file`].
+#[must_use]
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
+    try_read_file(folder, puzzle).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Like [`read_file`], for puzzles with more than one example.
+///
+/// # Panics
+/// Panics with the path that was tried if the file cannot be read, see [`try_read_example`].
+#[must_use]
+pub fn read_example(folder: &str, puzzle: PuzzleId, example: u8) -> String {
+    try_read_example(folder, puzzle, example).unwrap_or_else(|e| panic!("{e}"))
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Also registers the solution as `SOLUTION` for the in-process `all` binary.
+///
+/// The year of `DAY` is taken from the directory of the solution file, e.g. `src/bin/2023/01.rs`.
+///
+/// Use `solution!(<day>)` for free `part_one` / `part_two` functions that take the raw input,
+/// or `solution!(<day>, <type>)` for a type that implements [`Solution`](crate::Solution).
 #[macro_export]
 macro_rules! solution {
+    (@register $day:expr, $runner:expr) => {
+        /// The current puzzle.
+        const DAY: advent_of_code::PuzzleId = advent_of_code::PuzzleId::new(
+            advent_of_code::Year::__from_path(file!()),
+            advent_of_code::day!($day),
+        );
+
+        /// Registry entry for this day, see `advent_of_code::template::registry`.
+        #[allow(dead_code)]
+        pub const SOLUTION: advent_of_code::template::registry::SolutionEntry =
+            advent_of_code::template::registry::SolutionEntry {
+                puzzle: DAY,
+                runner: $runner,
+            };
+    };
     ($day:expr) => {
-        /// The current day.
-        const DAY: advent_of_code::Day = advent_of_code::day!($day);
+        advent_of_code::solution!(@register $day, |input, format| {
+            use advent_of_code::template::runner::solve_part;
+            let input: std::sync::Arc<str> = input.into();
+            vec![
+                solve_part(part_one, &input, DAY, 1, format),
+                solve_part(part_two, &input, DAY, 2, format),
+            ]
+        });
 
+        // unused when the day is compiled into the `all` binary.
+        #[allow(dead_code)]
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let input: std::sync::Arc<str> = read_input(DAY).into();
+            let one = run_part(part_one, &input, DAY, 1);
+            let two = run_part(part_two, &input, DAY, 2);
+            one.and(two).unwrap_or_else(|e| e.exit());
         }
     };
+    ($day:expr, $solution:ty) => {
+        advent_of_code::solution!(@register $day, |input, format| {
+            advent_of_code::template::runner::solve_solution::<$solution>(input, DAY, format)
+        });
+
+        // unused when the day is compiled into the `all` binary.
+        #[allow(dead_code)]
+        fn main() {
+            let input = advent_of_code::template::runner::read_input(DAY);
+            advent_of_code::template::runner::run_solution::<$solution>(&input, DAY)
+                .unwrap_or_else(|e| e.exit());
+        }
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_data_root, get_data_dir, get_data_path, try_read_example};
+    use crate::{day, PuzzleId, Year};
+
+    #[test]
+    fn finds_data_root() {
+        assert!(find_data_root().join("data").is_dir());
+    }
+
+    #[test]
+    fn roots_data_paths() {
+        let puzzle = PuzzleId::new(Year::__new_unchecked(2023), day!(1));
+        let path = get_data_path("inputs", puzzle, "txt");
+        assert_eq!(path, find_data_root().join("data/2023/inputs/01.txt"));
+        assert!(get_data_dir(puzzle.year).is_absolute());
+    }
+
+    #[test]
+    fn reports_missing_files() {
+        let puzzle = PuzzleId::new(Year::__new_unchecked(2015), day!(25));
+        let err = try_read_example("examples", puzzle, 9).unwrap_err();
+        assert!(err.is_not_found());
+        assert!(err.path.is_absolute());
+        assert!(err.path.ends_with("data/2015/examples/25-9.txt"));
+    }
 }
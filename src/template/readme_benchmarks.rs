@@ -1,131 +1,227 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::fs;
 
-use crate::Day;
+use crate::template::benchmark_chart::{self, CHART_PATH};
+use crate::template::commands::examples::get_module_path;
+use crate::template::Error;
+use crate::{Day, PuzzleId, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
-#[derive(Debug)]
-pub enum Error {
-    Parser(String),
-    IO(io::Error),
-}
-
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
-}
-
 #[derive(Clone)]
 pub struct Timings {
-    pub day: Day,
+    pub puzzle: PuzzleId,
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// Highest peak memory of the steps, only measured with the `alloc_stats` feature.
+    pub memory: Option<String>,
     pub total_nanos: f64,
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
-}
-
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    pub(crate) pos_start: usize,
+    pub(crate) pos_end: usize,
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Finds the table between two occurences of `marker`.
+/// A single marker is replaced by the table, which starts and ends with the marker.
+pub(crate) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parse(format!(
+            "too many occurences of marker \"{marker}\" in README."
+        )));
     }
 
     let pos_start = matches
         .first()
         .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+        .ok_or_else(|| Error::Parse(format!("could not find marker \"{marker}\" in README.")))?;
 
     let pos_end = matches
         .last()
         .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+        .ok_or_else(|| Error::Parse(format!("could not find marker \"{marker}\" in README.")))?;
 
     Ok(TablePosition { pos_start, pos_end })
 }
 
+/// The peak memory column is only added if any of the days has allocation stats.
 fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
+    let has_memory = timings.iter().any(|timing| timing.memory.is_some());
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    if has_memory {
+        lines.push("| Day | Parse | Part 1 | Part 2 | Peak memory |".into());
+        lines.push("| :---: | :---: | :---: | :---: | :---: |".into());
+    } else {
+        lines.push("| Day | Parse | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
+        let mut row = format!(
+            "| [Day {}](./{}) | `{}` | `{}` | `{}` |",
+            timing.puzzle.day.into_inner(),
+            get_module_path(timing.puzzle).display(),
+            timing.parse.unwrap_or_else(|| "-".into()),
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        );
+        if has_memory {
+            row.push_str(&format!(
+                " `{}` |",
+                timing.memory.unwrap_or_else(|| "-".into())
+            ));
+        }
+        lines.push(row);
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+    lines.push(String::new());
+    lines.push(format!("![Benchmark chart](./{CHART_PATH})"));
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// Reads durations formatted as `{:.1?}` back into nanoseconds.
+pub(crate) fn parse_duration(s: &str) -> Option<f64> {
+    [("ns", 1.0), ("µs", 1e3), ("ms", 1e6), ("s", 1e9)]
+        .into_iter()
+        .find_map(|(unit, factor)| Some(s.strip_suffix(unit)?.parse::<f64>().ok()? * factor))
+}
+
+/// Reads a row of an existing table, returns [`None`] for other rows and for days of another year.
+fn parse_row(line: &str, year: Year) -> Option<Timings> {
+    let cells: Vec<&str> = line
+        .trim()
+        .strip_prefix('|')?
+        .strip_suffix('|')?
+        .split('|')
+        .map(str::trim)
+        .collect();
+
+    let (link, times) = cells.split_first()?;
+    let day: Day = link
+        .strip_prefix("[Day ")?
+        .split_once(']')?
+        .0
+        .parse()
+        .ok()?;
+
+    // tables written before multi-year support link to `./src/bin/<day>.rs`.
+    let link_year = link.split('/').find_map(|s| s.parse::<Year>().ok());
+    if link_year.is_some_and(|link_year| link_year != year) {
+        return None;
+    }
+
+    let time = |cell: &str| {
+        let time = cell.trim_matches('`');
+        (time != "-").then(|| time.to_string())
+    };
+
+    let (parse, part_1, part_2, memory) = match times {
+        [parse, part_1, part_2, memory] => (time(parse), time(part_1), time(part_2), time(memory)),
+        [parse, part_1, part_2] => (time(parse), time(part_1), time(part_2), None),
+        [part_1, part_2] => (None, time(part_1), time(part_2), None),
+        _ => return None,
+    };
+
+    let total_nanos = [&parse, &part_1, &part_2]
+        .into_iter()
+        .flatten()
+        .filter_map(|time| parse_duration(time))
+        .sum();
+
+    Some(Timings {
+        puzzle: PuzzleId::new(year, day),
+        parse,
+        part_1,
+        part_2,
+        memory,
+        total_nanos,
+    })
+}
+
+/// Merges new timings into the rows of an existing table, rows of days that were not run are kept.
+fn merge_timings(existing: &str, year: Year, timings: Vec<Timings>) -> Vec<Timings> {
+    let mut merged: Vec<Timings> = existing
+        .lines()
+        .filter_map(|line| parse_row(line, year))
+        .filter(|row| timings.iter().all(|timing| timing.puzzle != row.puzzle))
+        .collect();
+
+    merged.extend(timings);
+    merged.sort_by_key(|timing| timing.puzzle);
+    merged
+}
+
+/// Updates the table in `s`, returns the timings of all rows in the updated table.
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Vec<Timings>,
+) -> Result<Vec<Timings>, Error> {
+    let positions = locate_table(s, MARKER)?;
+    let timings = merge_timings(&s[positions.pos_start..positions.pos_end], year, timings);
+    let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+    let table = construct_table("##", timings.clone(), total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+    Ok(timings)
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+/// Updates the benchmark table in the readme with the timings of `year`.
+/// Days that were not run keep their previous timings, the total is calculated over the whole table.
+/// The chart linked from the table is redrawn from the same rows.
+pub fn update(year: Year, timings: Vec<Timings>) -> Result<(), Error> {
     let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+    let bytes = fs::read(path).map_err(|e| Error::io(path, e))?;
+    let mut readme = String::from_utf8_lossy(&bytes).to_string();
+    let timings = update_content(&mut readme, year, timings)?;
+    fs::write(path, &readme).map_err(|e| Error::io(path, e))?;
+    benchmark_chart::write(&timings).map_err(|e| Error::io(CHART_PATH, e))?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use super::{parse_duration, update_content, Timings, MARKER};
+    use crate::{day, PuzzleId, Year};
+
+    const YEAR: Year = Year::__new_unchecked(2023);
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
-                day: day!(1),
+                puzzle: PuzzleId::new(YEAR, day!(1)),
+                parse: None,
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
-                total_nanos: 3e+10,
+                memory: None,
+                total_nanos: 3e+7,
             },
             Timings {
-                day: day!(2),
+                puzzle: PuzzleId::new(YEAR, day!(2)),
+                parse: Some("5ms".into()),
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
-                total_nanos: 7e+10,
+                memory: None,
+                total_nanos: 7.5e+7,
             },
             Timings {
-                day: day!(4),
+                puzzle: PuzzleId::new(YEAR, day!(4)),
+                parse: None,
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
-                total_nanos: 9e+10,
+                memory: None,
+                total_nanos: 9e+7,
             },
         ]
     }
@@ -134,28 +230,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, YEAR, get_mock_timings()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, YEAR, get_mock_timings()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, YEAR, get_mock_timings()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, YEAR, get_mock_timings()).unwrap();
+        update_content(&mut s, YEAR, get_mock_timings()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -163,24 +259,101 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, YEAR, get_mock_timings()).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2023/01.rs) | `-` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2023/02.rs) | `5ms` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2023/04.rs) | `-` | `40ms` | `50ms` |",
+            "",
+            "**Total: 195.00ms**",
             "",
-            "**Total: 190.00ms**",
+            "![Benchmark chart](./.assets/benchmarks.svg)",
             "<!--- benchmarking table --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn merges_partial_results() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, YEAR, get_mock_timings()).unwrap();
+
+        let rerun = Timings {
+            puzzle: PuzzleId::new(YEAR, day!(2)),
+            parse: None,
+            part_1: Some("1.0ms".into()),
+            part_2: Some("2.0ms".into()),
+            memory: None,
+            total_nanos: 3e+6,
+        };
+        update_content(&mut s, YEAR, vec![rerun]).unwrap();
+
+        assert!(s.contains("| [Day 1](./src/bin/2023/01.rs) | `-` | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/2023/02.rs) | `-` | `1.0ms` | `2.0ms` |"));
+        assert!(s.contains("| [Day 4](./src/bin/2023/04.rs) | `-` | `40ms` | `50ms` |"));
+        assert!(s.contains("**Total: 123.00ms**"));
+    }
+
+    #[test]
+    fn merges_tables_without_parse_column() {
+        let mut s = [
+            MARKER,
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 3](./src/bin/03.rs) | `41.7µs` | `1.5s` |",
+            MARKER,
+        ]
+        .join("\n");
+        update_content(&mut s, YEAR, get_mock_timings()).unwrap();
+
+        assert!(s.contains("| [Day 3](./src/bin/2023/03.rs) | `-` | `41.7µs` | `1.5s` |"));
+        assert!(s.contains("**Total: 1695.04ms**"));
+    }
+
+    #[test]
+    fn adds_memory_column() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings[1].memory = Some("1.5 KiB".into());
+        update_content(&mut s, YEAR, timings).unwrap();
+
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 | Peak memory |"));
+        assert!(s.contains("| [Day 1](./src/bin/2023/01.rs) | `-` | `10ms` | `20ms` | `-` |"));
+        assert!(
+            s.contains("| [Day 2](./src/bin/2023/02.rs) | `5ms` | `30ms` | `40ms` | `1.5 KiB` |")
+        );
+
+        // rows with a memory column are read back when other days are rerun.
+        let rerun = get_mock_timings().remove(0);
+        update_content(&mut s, YEAR, vec![rerun]).unwrap();
+        assert!(s.contains("| `1.5 KiB` |"));
+    }
+
+    #[test]
+    fn drops_rows_of_other_years() {
+        let mut s = format!(
+            "{}\n| [Day 3](./src/bin/2015/03.rs) | `-` | `1ms` | `1ms` |\n{}",
+            MARKER, MARKER
+        );
+        update_content(&mut s, YEAR, get_mock_timings()).unwrap();
+        assert!(!s.contains("Day 3"));
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("350.0ns"), Some(350.0));
+        assert_eq!(parse_duration("41.5µs"), Some(41_500.0));
+        assert_eq!(parse_duration("74.1ms"), Some(74_100_000.0));
+        assert_eq!(parse_duration("2.0s"), Some(2e9));
+        assert_eq!(parse_duration("-"), None);
+    }
 }
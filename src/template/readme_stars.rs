@@ -0,0 +1,286 @@
+/// Module that updates the readme with a table of earned stars.
+/// The table has the same format as the one written by `advent-readme-stars`, so both can be used interchangeably.
+use std::collections::HashMap;
+use std::fs;
+
+use serde::Deserialize;
+
+use crate::template::readme_benchmarks::locate_table;
+use crate::template::submissions::{self, Ledger, Outcome};
+use crate::template::{Context, Error};
+use crate::{all_days, Day, PuzzleId, Year};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// A star earned for one part of a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Star {
+    /// Unix timestamp in seconds, unknown for answers that were added to the ledger by hand.
+    pub earned_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayStars {
+    pub day: Day,
+    pub part_1: Option<Star>,
+    pub part_2: Option<Star>,
+}
+
+impl DayStars {
+    /// Returns the time the most recent star of the day was earned.
+    fn completed_at(&self) -> Option<u64> {
+        [self.part_1, self.part_2]
+            .into_iter()
+            .flatten()
+            .filter_map(|star| star.earned_at)
+            .max()
+    }
+}
+
+/// Collects the stars of `year` from the correct answers in the submission ledgers.
+pub fn from_ledgers(year: Year) -> Result<Vec<DayStars>, Error> {
+    let mut stars = vec![];
+
+    for day in all_days(year) {
+        let puzzle = PuzzleId::new(year, day);
+        let ledger = Ledger::load(puzzle).with_context(|| {
+            format!(
+                "failed to read \"{}\"",
+                submissions::get_path(puzzle).display()
+            )
+        })?;
+
+        let star = |part: u8| {
+            ledger.correct_answer(part)?;
+            let earned_at = ledger
+                .submissions
+                .iter()
+                .find(|s| s.part == part && s.outcome == Outcome::Correct)
+                .map(|s| s.submitted_at);
+            Some(Star { earned_at })
+        };
+
+        let day_stars = DayStars {
+            day,
+            part_1: star(1),
+            part_2: star(2),
+        };
+
+        if day_stars.part_1.is_some() || day_stars.part_2.is_some() {
+            stars.push(day_stars);
+        }
+    }
+
+    Ok(stars)
+}
+
+#[derive(Deserialize)]
+struct Leaderboard {
+    members: HashMap<String, Member>,
+}
+
+#[derive(Deserialize)]
+struct Member {
+    #[serde(default)]
+    completion_day_level: HashMap<String, HashMap<String, StarInfo>>,
+}
+
+#[derive(Deserialize)]
+struct StarInfo {
+    get_star_ts: u64,
+}
+
+/// Collects the stars of a member from a private leaderboard, as served by
+/// `https://adventofcode.com/<year>/leaderboard/private/view/<id>.json`.
+/// The member can be left out if the leaderboard has only one member.
+pub fn from_leaderboard(json: &str, user_id: Option<&str>) -> Result<Vec<DayStars>, Error> {
+    let leaderboard: Leaderboard =
+        serde_json::from_str(json).map_err(|e| Error::Parse(e.to_string()))?;
+
+    let member = match user_id {
+        Some(id) => leaderboard.members.get(id),
+        None if leaderboard.members.len() == 1 => leaderboard.members.values().next(),
+        None => {
+            return Err(Error::Parse(
+                "leaderboard has more than one member, set \"AOC_USER_ID\".".into(),
+            ))
+        }
+    }
+    .ok_or_else(|| Error::Parse("member not found in leaderboard.".into()))?;
+
+    let mut stars: Vec<DayStars> = member
+        .completion_day_level
+        .iter()
+        .filter_map(|(day, parts)| {
+            let star = |part: &str| {
+                parts.get(part).map(|info| Star {
+                    earned_at: Some(info.get_star_ts),
+                })
+            };
+
+            Some(DayStars {
+                day: day.parse().ok()?,
+                part_1: star("1"),
+                part_2: star("2"),
+            })
+        })
+        .collect();
+
+    stars.sort_by_key(|stars| stars.day);
+    Ok(stars)
+}
+
+/// Formats a unix timestamp as `YYYY-MM-DD HH:MM` in UTC.
+fn format_timestamp(timestamp: u64) -> String {
+    let (days, secs) = (timestamp / 86_400, timestamp % 86_400);
+
+    // see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        secs / 3_600,
+        secs % 3_600 / 60
+    )
+}
+
+fn construct_table(prefix: &str, year: Year, stars: &[DayStars]) -> String {
+    let header = format!("{prefix} {year} Results");
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        header,
+        String::new(),
+        "| Day | Part 1 | Part 2 | Completed |".into(),
+        "| :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    let star = |star: Option<Star>| if star.is_some() { "⭐" } else { "" };
+
+    for day in stars {
+        lines.push(format!(
+            "| [Day {}](https://adventofcode.com/{year}/day/{}) | {} | {} | {} |",
+            day.day.into_inner(),
+            day.day.into_inner(),
+            star(day.part_1),
+            star(day.part_2),
+            day.completed_at().map(format_timestamp).unwrap_or_default()
+        ));
+    }
+
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, year: Year, stars: &[DayStars]) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", year, stars);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update(year: Year, stars: &[DayStars]) -> Result<(), Error> {
+    let path = "README.md";
+    let bytes = fs::read(path).map_err(|e| Error::io(path, e))?;
+    let mut readme = String::from_utf8_lossy(&bytes).to_string();
+    update_content(&mut readme, year, stars)?;
+    fs::write(path, &readme).map_err(|e| Error::io(path, e))?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_timestamp, from_leaderboard, update_content, DayStars, Star, MARKER};
+    use crate::{day, Year};
+
+    const YEAR: Year = Year::__new_unchecked(2023);
+
+    fn get_mock_stars() -> Vec<DayStars> {
+        vec![
+            DayStars {
+                day: day!(1),
+                part_1: Some(Star {
+                    earned_at: Some(1701416400),
+                }),
+                part_2: Some(Star {
+                    earned_at: Some(1701417000),
+                }),
+            },
+            DayStars {
+                day: day!(2),
+                part_1: Some(Star { earned_at: None }),
+                part_2: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(1701416400), "2023-12-01 07:40");
+        assert_eq!(format_timestamp(1709251199), "2024-02-29 23:59");
+        assert_eq!(format_timestamp(951782400), "2000-02-29 00:00");
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{}\nbar", MARKER);
+        update_content(&mut s, YEAR, &get_mock_stars()).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 | Completed |",
+            "| :---: | :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ | 2023-12-01 07:50 |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ |  |  |",
+            "<!--- advent_readme_stars table --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+
+        update_content(&mut s, YEAR, &get_mock_stars()).unwrap();
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn reads_leaderboard() {
+        let json = r#"{
+            "event": "2023",
+            "owner_id": 1,
+            "members": {
+                "1": {
+                    "id": 1,
+                    "stars": 3,
+                    "completion_day_level": {
+                        "2": { "1": { "get_star_ts": 1701500000, "star_index": 3 } },
+                        "1": {
+                            "1": { "get_star_ts": 1701416400, "star_index": 1 },
+                            "2": { "get_star_ts": 1701417000, "star_index": 2 }
+                        }
+                    }
+                },
+                "2": { "id": 2, "stars": 0, "completion_day_level": {} }
+            }
+        }"#;
+
+        let stars = from_leaderboard(json, Some("1")).unwrap();
+        assert_eq!(stars.len(), 2);
+        assert_eq!(stars[0].day, day!(1));
+        assert_eq!(stars[0].completed_at(), Some(1701417000));
+        assert_eq!(stars[1].part_2, None);
+
+        assert!(from_leaderboard(json, None).is_err());
+        assert!(from_leaderboard(json, Some("3")).is_err());
+    }
+}
@@ -0,0 +1,41 @@
+/// Table of solutions that can be run in-process by the `all` binary.
+/// Entries are created by the `solution!` macro, the table itself is generated by `build.rs`.
+use crate::template::runner::{OutputFormat, PartRecord};
+use crate::PuzzleId;
+
+/// Runs and times every part of a solution against the given input.
+pub type SolutionRunner = fn(&str, OutputFormat) -> Vec<PartRecord>;
+
+/// A registered solution for one puzzle.
+#[derive(Clone, Copy)]
+pub struct SolutionEntry {
+    pub puzzle: PuzzleId,
+    pub runner: SolutionRunner,
+}
+
+impl SolutionEntry {
+    #[must_use]
+    pub fn run(&self, input: &str, format: OutputFormat) -> Vec<PartRecord> {
+        (self.runner)(input, format)
+    }
+}
+
+/// All solutions that were compiled into the current binary.
+pub struct Registry(&'static [SolutionEntry]);
+
+impl Registry {
+    #[must_use]
+    pub const fn new(entries: &'static [SolutionEntry]) -> Self {
+        Self(entries)
+    }
+
+    /// Returns the solution registered for `puzzle`, if any.
+    #[must_use]
+    pub fn get(&self, puzzle: PuzzleId) -> Option<&SolutionEntry> {
+        self.0.iter().find(|entry| entry.puzzle == puzzle)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &SolutionEntry> {
+        self.0.iter()
+    }
+}
@@ -1,79 +1,787 @@
 /// Encapsulates code that interacts with solution functions.
+use crate::template::alloc_stats::{self, format_bytes, AllocStats};
+use crate::template::aoc_client::Verdict;
+use crate::template::submissions::{self, Ledger, Refusal};
 use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::{Day, PuzzleId, Solution, Year};
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
 use std::fmt::Display;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{self, stdout, Read, Write};
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{mpsc, Arc, Once};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, thread};
 
-use super::ANSI_BOLD;
+use super::{try_read_example, try_read_file, Context, Error, ReadFileError, ANSI_BOLD};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Where a solution binary reads its input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// The real input, `data/<year>/inputs/<day>.txt`.
+    Default,
+    /// `--input -`, reads the input from stdin.
+    Stdin,
+    /// `--input <path>` or the `AOC_INPUT` environment variable.
+    File(PathBuf),
+    /// `--example <n>`, reads `data/<year>/examples/<day>-<n>.txt`.
+    Example(u8),
+}
+
+impl InputSource {
+    /// Reads the input source from the arguments of a solution binary.
+    /// Flags take precedence over `AOC_INPUT`.
+    fn from_args() -> Result<Self, String> {
+        let args: Vec<String> = env::args().collect();
+        Self::parse(&args, env::var("AOC_INPUT").ok())
+    }
+
+    fn parse(args: &[String], env_input: Option<String>) -> Result<Self, String> {
+        let value = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .map(|i| {
+                    args.get(i + 1)
+                        .ok_or(format!("missing value for `{flag}`."))
+                })
+                .transpose()
+        };
+
+        let source = match (value("--input")?, value("--example")?) {
+            (Some(_), Some(_)) => {
+                return Err("`--input` and `--example` cannot be combined.".into())
+            }
+            (Some(path), None) => Self::from_path(path),
+            (None, Some(example)) => Self::Example(
+                example
+                    .parse()
+                    .map_err(|_| format!("invalid example number \"{example}\"."))?,
+            ),
+            (None, None) => env_input
+                .filter(|path| !path.is_empty())
+                .map_or(Self::Default, |path| Self::from_path(&path)),
+        };
+
+        Ok(source)
+    }
+
+    fn from_path(path: &str) -> Self {
+        if path == "-" {
+            Self::Stdin
+        } else {
+            Self::File(PathBuf::from(path))
+        }
+    }
+
+    /// Reads the input of `puzzle` from this source.
+    pub fn read(&self, puzzle: PuzzleId) -> Result<String, ReadFileError> {
+        match self {
+            Self::Default => try_read_file("inputs", puzzle),
+            Self::Example(example) => try_read_example("examples", puzzle, *example),
+            Self::File(path) => fs::read_to_string(path).map_err(|source| ReadFileError {
+                path: path.clone(),
+                source,
+            }),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|source| ReadFileError {
+                        path: PathBuf::from("<stdin>"),
+                        source,
+                    })?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// Reads the input of a puzzle for its solution binary, see [`InputSource`] for the options.
+/// Exits with a hint to download the input if the real input does not exist yet.
+pub fn read_input(puzzle: PuzzleId) -> String {
+    try_read_input(puzzle).unwrap_or_else(|e| e.exit())
+}
+
+fn try_read_input(puzzle: PuzzleId) -> Result<String, Error> {
+    let source = InputSource::from_args().map_err(Error::Usage)?;
+
+    // answers to other inputs than the real one would be rejected, or worse, lock out the part.
+    if source != InputSource::Default && env::args().any(|x| x == "--submit") {
+        return Err(Error::Usage(
+            "`--submit` can only be used with the real input.".into(),
+        ));
+    }
+
+    match source.read(puzzle) {
+        Ok(input) => Ok(input),
+        Err(e) if e.is_not_found() && source == InputSource::Default => Err(Error::MissingInput {
+            puzzle,
+            path: e.path,
+        }),
+        Err(e) => Err(e).context("failed to read input"),
+    }
+}
+
+/// Runs one part for its solution binary and submits the answer if requested.
+///
+/// # Errors
+/// Returns an error if the submission fails or the answer is wrong.
+pub fn run_part<X, T>(
+    func: impl Fn(&X) -> Option<T> + Copy + Send + 'static,
+    input: &Arc<X>,
+    puzzle: PuzzleId,
+    part: u8,
+) -> Result<(), Error>
+where
+    X: ?Sized + Send + Sync + 'static,
+    T: Display + Send + 'static,
+{
+    let format = OutputFormat::from_args();
+    let record = solve_part(func, input, puzzle, part, format);
+
+    if format != OutputFormat::Text {
+        println!("{}", record.to_json());
+    }
+
+    match record.answer {
+        Some(answer) => submit_result(answer, puzzle, part, format),
+        None => Ok(()),
+    }
+}
+
+/// Part number of the records that describe the parse step of a [`Solution`].
+pub const PARSE_PART: u8 = 0;
+
+/// Runs a [`Solution`] for its solution binary and submits an answer if requested.
+///
+/// # Errors
+/// Returns an error if the submission fails or the answer is wrong.
+pub fn run_solution<S: Solution + 'static>(input: &str, puzzle: PuzzleId) -> Result<(), Error> {
+    let format = OutputFormat::from_args();
+    let mut result = Ok(());
+
+    for record in solve_solution::<S>(input, puzzle, format) {
+        if format != OutputFormat::Text {
+            println!("{}", record.to_json());
+        }
+
+        if let (Some(answer), true) = (record.answer, record.part != PARSE_PART) {
+            result = result.and(submit_result(answer, puzzle, record.part, format));
+        }
+    }
+
+    result
+}
+
+/// Parses the input once and runs both parts of a [`Solution`] against it.
+/// The parse step is timed separately and reported as part [`PARSE_PART`].
+pub fn solve_solution<S: Solution + 'static>(
+    input: &str,
+    puzzle: PuzzleId,
+    format: OutputFormat,
+) -> Vec<PartRecord> {
+    let is_text = format == OutputFormat::Text;
+    let input: Arc<str> = Arc::from(input);
+
+    let (parsed, report) = run_timed(S::parse, &input, is_text, |parsed| {
+        if is_text {
+            print_parse_result(parsed.as_ref().err(), None);
+        }
+    });
+
+    // a panic or timeout of the parse step is reported like a parse error.
+    let parsed = parsed
+        .map_err(|failure| failure.to_string())
+        .and_then(|parsed| parsed.map_err(|e| e.to_string()));
+
+    if is_text {
+        print_parse_result(parsed.as_ref().err(), Some(&report));
+    }
+
+    let parse_record = PartRecord {
+        year: puzzle.year,
+        day: puzzle.day,
+        part: PARSE_PART,
+        answer: None,
+        success: parsed.is_ok(),
+        error: parsed.as_ref().err().cloned(),
+        timing: report,
+    };
+
+    let Ok(parsed) = parsed else {
+        return vec![parse_record];
+    };
+
+    let parsed = Arc::new(parsed);
+
+    vec![
+        parse_record,
+        solve_part(S::part_one, &parsed, puzzle, 1, format),
+        solve_part(S::part_two, &parsed, puzzle, 2, format),
+    ]
+}
+
+/// Runs and times a solution part, printing progress and the result if `format` is [`OutputFormat::Text`].
+/// A part that panics or exceeds `--timeout` is reported as failed, with the reason in [`PartRecord::error`].
+/// The input is shared, as a part that timed out keeps running in the background with its own reference.
+pub fn solve_part<X, T>(
+    func: impl Fn(&X) -> Option<T> + Copy + Send + 'static,
+    input: &Arc<X>,
+    puzzle: PuzzleId,
+    part: u8,
+    format: OutputFormat,
+) -> PartRecord
+where
+    X: ?Sized + Send + Sync + 'static,
+    T: Display + Send + 'static,
+{
     let part_str = format!("Part {part}");
+    let is_text = format == OutputFormat::Text;
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, report) = run_timed(func, input, is_text, |result| {
+        if is_text {
+            print_result(result, &part_str, None);
+        }
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let (answer, error) = match result {
+        Ok(result) => {
+            if is_text {
+                print_result(&result, &part_str, Some(&report));
+            }
+            (result.as_ref().map(ToString::to_string), None)
+        }
+        Err(failure) => {
+            let error = failure.to_string();
+            if is_text {
+                print_failure(&part_str, &error);
+            }
+            (None, Some(error))
+        }
+    };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    PartRecord {
+        year: puzzle.year,
+        day: puzzle.day,
+        part,
+        success: answer.is_some(),
+        answer,
+        error,
+        timing: report,
+    }
+}
+
+/// Reason why a solution step did not return.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// The step panicked, with the panic message and location.
+    Panicked(String),
+    /// The step did not return within `--timeout`, it is left running in the background.
+    TimedOut(Duration),
+    /// The thread that runs the step with a timeout could not be started.
+    SpawnFailed(String),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Panicked(message) => write!(f, "panicked at {message}"),
+            Failure::TimedOut(timeout) => write!(f, "timed out after {timeout:.1?}"),
+            Failure::SpawnFailed(error) => write!(f, "could not start a thread: {error}"),
+        }
+    }
+}
+
+/// Stack size of the threads that run solution steps with a timeout, matches the main thread on Linux.
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+thread_local! {
+    /// Whether panics on this thread are caught by [`call_guarded`].
+    static CATCH_PANICS: Cell<bool> = const { Cell::new(false) };
+    /// Message and location of the last panic caught on this thread.
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Reads `--timeout <seconds>` from the command-line.
+fn timeout_from_args() -> Option<Duration> {
+    let args: Vec<String> = env::args().collect();
+    args.iter()
+        .position(|x| x == "--timeout")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|x| x.parse::<f64>().ok())
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+}
+
+/// Installs a panic hook that records panics of guarded calls instead of printing them.
+/// Panics outside of [`call_guarded`] are passed on to the previous hook.
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCH_PANICS.get() {
+                LAST_PANIC.set(Some(describe_panic(info)));
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+fn describe_panic(info: &PanicHookInfo) -> String {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>");
+
+    match info.location() {
+        Some(location) => format!("{location}: {message}"),
+        None => message.to_string(),
+    }
+}
+
+/// Calls `func`, turning a panic into [`Failure::Panicked`].
+fn call_guarded<I, T>(func: impl Fn(I) -> T, input: I) -> Result<T, Failure> {
+    install_panic_hook();
+
+    CATCH_PANICS.set(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(|| func(input)));
+    CATCH_PANICS.set(false);
+
+    result.map_err(|_| {
+        Failure::Panicked(
+            LAST_PANIC
+                .take()
+                .unwrap_or_else(|| "unknown location".into()),
+        )
+    })
+}
+
+/// Result, duration and allocations of a single call of a solution step.
+type Isolated<T> = (Result<T, Failure>, Duration, Option<AllocStats>);
+
+/// Runs `work` on a thread of its own, so that the caller can stop waiting for it.
+fn spawn_worker(work: impl FnOnce() + Send + 'static) -> Result<(), Failure> {
+    thread::Builder::new()
+        .name("solution".into())
+        .stack_size(STACK_SIZE)
+        .spawn(work)
+        .map(|_| ())
+        .map_err(|e| Failure::SpawnFailed(e.to_string()))
+}
+
+/// Calls `func` once and measures it. Without a `timeout`, the call borrows `input` on the current thread.
+/// With a `timeout`, the call runs on its own thread with a reference to `input`;
+/// if it does not return in time, the thread is left running and [`Failure::TimedOut`] is returned.
+fn run_isolated<X, T>(
+    func: impl Fn(&X) -> T + Send + 'static,
+    input: &Arc<X>,
+    timeout: Option<Duration>,
+) -> Isolated<T>
+where
+    X: ?Sized + Send + Sync + 'static,
+    T: Send + 'static,
+{
+    let run = |func: &dyn Fn(&X) -> T, input: &X| {
+        let ((result, elapsed), allocs) = alloc_stats::measure(|| {
+            let timer = Instant::now();
+            let result = call_guarded(func, input);
+            (result, timer.elapsed())
+        });
+        (result, elapsed, allocs)
+    };
+
+    let Some(timeout) = timeout else {
+        return run(&func, input);
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let input = Arc::clone(input);
+    let spawned = spawn_worker(move || {
+        // the receiver is gone if the call timed out.
+        let _ = sender.send(run(&func, &input));
+    });
+
+    if let Err(failure) = spawned {
+        return (Err(failure), Duration::ZERO, None);
+    }
+
+    receiver
+        .recv_timeout(timeout)
+        .unwrap_or((Err(Failure::TimedOut(timeout)), timeout, None))
+}
+
+/// Output format of the `solve` and `all` commands.
+///
+/// Solution binaries emit one [`PartRecord`] per line for both [`OutputFormat::Json`]
+/// and [`OutputFormat::Ndjson`]; collecting records into a single document is up to the caller.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Ndjson,
+}
+
+impl OutputFormat {
+    /// Reads `--format <format>` from the command-line, falling back to [`OutputFormat::Text`].
+    pub(crate) fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        args.iter()
+            .position(|x| x == "--format")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+            OutputFormat::Ndjson => "ndjson",
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            _ => Err(format!(
+                "unknown format `{s}`, expecting one of: text, json, ndjson"
+            )),
+        }
+    }
+}
+
+/// Machine-readable result of running one part of a solution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartRecord {
+    pub year: Year,
+    pub day: Day,
+    pub part: u8,
+    /// The answer as displayed, [`None`] if the solution returned no result.
+    pub answer: Option<String>,
+    pub success: bool,
+    /// Describes why the part failed, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub timing: BenchReport,
+}
+
+impl PartRecord {
+    /// Serializes the record as a single line of JSON.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("part records are always serializable")
+    }
+
+    /// Parses a record from a single line of JSON, returns [`None`] for any other line.
+    #[must_use]
+    pub fn from_json(line: &str) -> Option<Self> {
+        serde_json::from_str(line).ok()
+    }
+
+    /// Renders the record the same way a text-mode run of the solution would.
+    pub fn print(&self) {
+        if self.part == PARSE_PART {
+            let error = (!self.success).then(|| self.error.as_deref().unwrap_or_default());
+            print_parse_result(error, Some(&self.timing));
+        } else if let Some(error) = &self.error {
+            print_failure(&format!("Part {}", self.part), error);
+        } else {
+            print_result(
+                &self.answer,
+                &format!("Part {}", self.part),
+                Some(&self.timing),
+            );
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+impl PartRecord {
+    /// A record of a 2023 part timed with `samples` in nanoseconds, the part succeeded if it has an answer.
+    #[must_use]
+    pub fn test(day: Day, part: u8, answer: Option<&str>, samples: &[u64]) -> Self {
+        let samples = samples.iter().copied().map(Duration::from_nanos).collect();
+        Self {
+            year: Year::__new_unchecked(2023),
+            day,
+            part,
+            answer: answer.map(Into::into),
+            success: answer.is_some(),
+            error: None,
+            timing: BenchReport::from_samples(samples, false),
+        }
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
-    input: I,
+///
+/// The first execution is isolated, see [`run_isolated`]; a part that fails is not benched.
+/// Allocations are counted for the first execution if the `alloc_stats` feature is enabled.
+fn run_timed<X, T>(
+    func: impl Fn(&X) -> T + Copy + Send + 'static,
+    input: &Arc<X>,
+    show_progress: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
+) -> (Result<T, Failure>, BenchReport)
+where
+    X: ?Sized + Send + Sync + 'static,
+    T: Send + 'static,
+{
+    let timeout = timeout_from_args();
+    let (result, base_time, allocs) = run_isolated(func, input, timeout);
+    let base_report = BenchReport::from_samples(vec![base_time], false);
+
+    let Ok(result) = result else {
+        return (result, base_report);
+    };
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let mut report = if std::env::args().any(|x| x == "--time") {
+        let config = BenchConfig::from_args();
+        match bench(func, input, &base_time, &config, show_progress, timeout) {
+            Ok(report) => report,
+            Err(failure) => return (Err(failure), base_report),
+        }
     } else {
-        (base_time, 1)
+        base_report
     };
 
-    (result, run.0, run.1)
+    report.allocs = allocs;
+    (Ok(result), report)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Settings that control how [`bench`] samples a solution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Number of untimed runs before sampling starts.
+    /// Defaults to a tenth of the sample count when not set.
+    pub warmup: Option<u128>,
+    /// Whether samples outside of the Tukey fences (1.5 × IQR) are discarded.
+    pub reject_outliers: bool,
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: None,
+            reject_outliers: true,
+        }
+    }
+}
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+impl BenchConfig {
+    /// Reads `--warmup <iterations>` and `--keep-outliers` from the command-line.
+    fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
 
-    let mut timers: Vec<Duration> = vec![];
+        let warmup = args
+            .iter()
+            .position(|x| x == "--warmup")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|x| x.parse().ok());
 
-    for _ in 0..bench_iterations {
-        // need a clone here to make the borrow checker happy.
-        let cloned = input.clone();
-        let timer = Instant::now();
-        func(cloned);
-        timers.push(timer.elapsed());
+        Self {
+            warmup,
+            reject_outliers: !args.iter().any(|x| x == "--keep-outliers"),
+        }
     }
+}
+
+/// Summary statistics of the samples collected while benching a solution part.
+///
+/// Durations serialize as integer nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchReport {
+    /// Number of samples the statistics are computed from.
+    pub samples: u128,
+    /// Number of samples that were discarded as outliers.
+    pub outliers: u128,
+    #[serde(rename = "min_ns", with = "nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", with = "nanos")]
+    pub median: Duration,
+    #[serde(rename = "mean_ns", with = "nanos")]
+    pub mean: Duration,
+    #[serde(rename = "p95_ns", with = "nanos")]
+    pub p95: Duration,
+    #[serde(rename = "max_ns", with = "nanos")]
+    pub max: Duration,
+    #[serde(rename = "std_dev_ns", with = "nanos")]
+    pub std_dev: Duration,
+    /// Allocations of a single run, only counted with the `alloc_stats` feature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocs: Option<AllocStats>,
+}
+
+mod nanos {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
 
-    (
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
         #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+        serializer.serialize_u64(duration.as_nanos() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let nanos = u64::deserialize(deserializer)?;
+        Ok(Duration::from_nanos(nanos))
+    }
+}
+
+impl BenchReport {
+    /// Computes the report for a set of samples, optionally discarding outliers first.
+    ///
+    /// # Panics
+    /// Panics if `samples` is empty.
+    #[must_use]
+    pub fn from_samples(mut samples: Vec<Duration>, reject_outliers: bool) -> Self {
+        assert!(!samples.is_empty(), "cannot build a report without samples");
+        samples.sort_unstable();
+
+        let total = samples.len();
+
+        if reject_outliers && total >= 4 {
+            let q1 = percentile(&samples, 25.0).as_nanos();
+            let q3 = percentile(&samples, 75.0).as_nanos();
+            let fence = (q3 - q1) * 3 / 2;
+            let lower = q1.saturating_sub(fence);
+            let upper = q3 + fence;
+            samples.retain(|x| (lower..=upper).contains(&x.as_nanos()));
+        }
+
+        let mean = average_duration(&samples);
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance = samples
+            .iter()
+            .map(|x| (x.as_nanos() as f64 - mean as f64).powi(2))
+            .sum::<f64>()
+            / samples.len() as f64;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Self {
+            samples: samples.len() as u128,
+            outliers: (total - samples.len()) as u128,
+            min: samples[0],
+            median: percentile(&samples, 50.0),
+            mean: Duration::from_nanos(mean as u64),
+            p95: percentile(&samples, 95.0),
+            max: samples[samples.len() - 1],
+            std_dev: Duration::from_nanos(variance.sqrt() as u64),
+            allocs: None,
+        }
+    }
+}
+
+/// Nearest-rank percentile of an already sorted, non-empty slice.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Calls `func` `count` times, passing the duration of each call or the reason it failed to `on_sample`.
+/// Stops after the first failure or when `on_sample` returns `false`.
+fn sample<X: ?Sized, T>(
+    func: impl Fn(&X) -> T,
+    input: &X,
+    count: u128,
+    mut on_sample: impl FnMut(Result<Duration, Failure>) -> bool,
+) {
+    for _ in 0..count {
+        let timer = Instant::now();
+        let result = call_guarded(&func, input).map(|_| timer.elapsed());
+        let failed = result.is_err();
+
+        if !on_sample(result) || failed {
+            return;
+        }
+    }
+}
+
+/// Benches a part that already returned once. Every call is guarded like the first one, so a part that
+/// panics or exceeds `timeout` in a later iteration fails instead of taking the process down.
+fn bench<X, T>(
+    func: impl Fn(&X) -> T + Send + 'static,
+    input: &Arc<X>,
+    base_time: &Duration,
+    config: &BenchConfig,
+    show_progress: bool,
+    timeout: Option<Duration>,
+) -> Result<BenchReport, Failure>
+where
+    X: ?Sized + Send + Sync + 'static,
+    T: 'static,
+{
+    if show_progress {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
+
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let warmup = config.warmup.unwrap_or(bench_iterations / 10);
+    let count = warmup + bench_iterations;
+
+    let mut timers: Vec<Duration> = vec![];
+    let mut record = |i: u128, result: Result<Duration, Failure>| -> Result<(), Failure> {
+        let elapsed = result?;
+        if i >= warmup {
+            timers.push(elapsed);
+        }
+        Ok(())
+    };
+
+    if let Some(timeout) = timeout {
+        // the samples are sent one by one, so that each call gets the full timeout.
+        let (sender, receiver) = mpsc::channel();
+        let shared = Arc::clone(input);
+        spawn_worker(move || {
+            sample(func, &shared, count, |result| sender.send(result).is_ok());
+        })?;
+
+        for i in 0..count {
+            let result = receiver
+                .recv_timeout(timeout)
+                .map_err(|_| Failure::TimedOut(timeout))?;
+            record(i, result)?;
+        }
+    } else {
+        let mut i = 0;
+        let mut failure = None;
+        sample(func, input, count, |result| {
+            failure = record(i, result).err();
+            i += 1;
+            failure.is_none()
+        });
+
+        if let Some(failure) = failure {
+            return Err(failure);
+        }
+    }
+
+    Ok(BenchReport::from_samples(timers, config.reject_outliers))
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -84,44 +792,92 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+/// Formats the headline timing of a report. The median is used as it is robust against outliers.
+fn format_duration(report: &BenchReport) -> String {
+    let median = report.median;
+    if report.samples == 1 {
+        format!(" ({median:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({median:.1?} @ {} samples)", report.samples)
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
-    let is_intermediate_result = duration_str.is_empty();
+/// Formats the spread of a report for display below the headline.
+fn format_spread(report: &BenchReport) -> String {
+    let BenchReport {
+        min,
+        mean,
+        p95,
+        max,
+        std_dev,
+        outliers,
+        ..
+    } = report;
+
+    format!(
+        "{ANSI_ITALIC}min {min:.1?} · mean {mean:.1?} · p95 {p95:.1?} · max {max:.1?} · σ {std_dev:.1?} · {outliers} outliers{ANSI_RESET}"
+    )
+}
+
+/// Formats the allocations of a report for display below the headline.
+fn format_allocs(allocs: &AllocStats) -> String {
+    format!(
+        "{ANSI_ITALIC}{} allocations · {} allocated · peak {}{ANSI_RESET}",
+        allocs.allocations,
+        format_bytes(allocs.bytes),
+        format_bytes(allocs.peak_bytes)
+    )
+}
+
+fn print_result<T: Display>(result: &Option<T>, part: &str, report: Option<&BenchReport>) {
+    let Some(report) = report else {
+        match result {
+            Some(result) if result.to_string().contains('\n') => print!("{part}: ▼ "),
+            Some(result) => print!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}"),
+            None => print!("{part}: ✖"),
+        }
+        return;
+    };
+
+    let duration_str = format_duration(report);
 
+    let details: Vec<String> = [
+        (report.samples > 1).then(|| format_spread(report)),
+        report.allocs.as_ref().map(format_allocs),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    print!("\r");
     match result {
         Some(result) => {
             if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
+                println!("{part}: ▼ {duration_str}");
+                details.iter().for_each(|s| println!("  {s}"));
+                println!("{result}");
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
+                println!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                details.iter().for_each(|s| println!("  {s}"));
             }
         }
         None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+            println!("{part}: ✖             ");
+        }
+    }
+}
+
+fn print_failure(part: &str, error: &str) {
+    println!("\r{part}: ✖ {ANSI_ITALIC}{error}{ANSI_RESET}");
+}
+
+fn print_parse_result(error: Option<impl Display>, report: Option<&BenchReport>) {
+    match error {
+        None => print_result(&Some("✔"), "Parse", report),
+        Some(error) => {
+            print_result(&None::<&str>, "Parse", report);
+            if report.is_some() {
+                eprintln!("{error}");
             }
         }
     }
@@ -129,39 +885,297 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured.
+///  3. the submission history does not rule out the answer, see [`Ledger::check`].
+///
+/// Messages go to stderr unless `format` is [`OutputFormat::Text`], so that stdout only contains records.
+/// A wrong answer is returned as [`Error::WrongAnswer`], also if the submission history already rules it out.
+/// A pending cooldown is returned as [`Error::Failed`].
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+    format: OutputFormat,
+) -> Result<(), Error> {
+    let say = |message: &str| {
+        if format == OutputFormat::Text {
+            println!("{message}");
+        } else {
+            eprintln!("{message}");
+        }
+    };
+
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return Ok(());
     }
 
+    let usage =
+        || Error::Usage("unexpected command-line input. Format: cargo solve 1 --submit 1".into());
+
     if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+        return Err(usage());
     }
 
     let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let Some(part_submit) = args.get(part_index).and_then(|x| x.parse::<u8>().ok()) else {
+        return Err(usage());
     };
 
     if part_submit != part {
-        return None;
+        return Ok(());
+    }
+
+    aoc_cli::check()?;
+
+    let answer = result.to_string();
+
+    let mut ledger = Ledger::load(puzzle).context("failed to read submission history")?;
+
+    if let Err(refusal) = ledger.check(part, &answer, submissions::now()) {
+        let message = format!("not submitting, {refusal}");
+        return match refusal {
+            Refusal::AlreadySolved(_) => {
+                say(&format!("Not submitting: {refusal}"));
+                Ok(())
+            }
+            Refusal::Cooldown(_) => Err(Error::Failed(message)),
+            Refusal::KnownWrong(_) | Refusal::AboveBound(_) | Refusal::BelowBound(_) => {
+                Err(Error::WrongAnswer(message))
+            }
+        };
+    }
+
+    say("Submitting result...");
+    let verdict = aoc_cli::submit(puzzle, part, &answer)
+        .map_err(Error::from)
+        .context("failed to submit result")?;
+
+    ledger.record(part, &answer, &verdict, submissions::now());
+    if let Err(e) = ledger.save(puzzle) {
+        eprintln!("Failed to write submission history: {e}");
+    }
+
+    let wrong = |message: &str| Err(Error::WrongAnswer(message.into()));
+
+    match verdict {
+        Verdict::Correct => say("That's the right answer! ⭐️"),
+        Verdict::TooHigh(_) => {
+            return wrong("that's not the right answer, your answer is too high.")
+        }
+        Verdict::TooLow(_) => return wrong("that's not the right answer, your answer is too low."),
+        Verdict::Incorrect(_) => return wrong("that's not the right answer."),
+        Verdict::Wait(duration) => {
+            return Err(Error::Failed(format!(
+                "you gave an answer too recently, wait {duration:?} before trying again."
+            )))
+        }
+        Verdict::WrongLevel => say("This part is either solved already or not unlocked yet."),
+        Verdict::Unknown(text) => say(&text),
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        bench, call_guarded, run_isolated, solve_part, BenchConfig, BenchReport, Failure,
+        InputSource, OutputFormat, PartRecord,
+    };
+    use crate::{day, PuzzleId, Year};
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn report_single_sample() {
+        let report = BenchReport::from_samples(nanos(&[42]), true);
+        assert_eq!(report.samples, 1);
+        assert_eq!(report.outliers, 0);
+        assert_eq!(report.min, Duration::from_nanos(42));
+        assert_eq!(report.median, Duration::from_nanos(42));
+        assert_eq!(report.max, Duration::from_nanos(42));
+        assert_eq!(report.std_dev, Duration::ZERO);
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn report_statistics() {
+        let report = BenchReport::from_samples(nanos(&[5, 1, 4, 2, 3]), false);
+        assert_eq!(report.samples, 5);
+        assert_eq!(report.min, Duration::from_nanos(1));
+        assert_eq!(report.median, Duration::from_nanos(3));
+        assert_eq!(report.mean, Duration::from_nanos(3));
+        assert_eq!(report.p95, Duration::from_nanos(5));
+        assert_eq!(report.max, Duration::from_nanos(5));
+        assert_eq!(report.std_dev, Duration::from_nanos(1));
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn report_rejects_outliers() {
+        let samples = nanos(&[10, 11, 10, 12, 11, 10, 1000]);
+
+        let report = BenchReport::from_samples(samples.clone(), true);
+        assert_eq!(report.samples, 6);
+        assert_eq!(report.outliers, 1);
+        assert_eq!(report.max, Duration::from_nanos(12));
+
+        let report = BenchReport::from_samples(samples, false);
+        assert_eq!(report.outliers, 0);
+        assert_eq!(report.max, Duration::from_nanos(1000));
+    }
+
+    #[test]
+    fn record_round_trip() {
+        let record = PartRecord::test(day!(3), 2, Some("1 (2 @ 3 samples)"), &[10, 20, 30]);
+
+        let json = record.to_json();
+        assert!(json.contains(r#""year":2023,"day":3"#));
+        assert!(json.contains(r#""median_ns":20"#));
+        assert!(!json.contains("error"));
+        assert_eq!(PartRecord::from_json(&json), Some(record));
+        assert_eq!(PartRecord::from_json("Part 1: 42 (1ns)"), None);
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        let parse =
+            |a: &[&str], env: Option<&str>| InputSource::parse(&args(a), env.map(Into::into));
+
+        assert_eq!(parse(&["01"], None), Ok(InputSource::Default));
+        assert_eq!(parse(&["01"], Some("")), Ok(InputSource::Default));
+        assert_eq!(parse(&["01", "--input", "-"], None), Ok(InputSource::Stdin));
+        assert_eq!(
+            parse(&["01", "--input", "big.txt", "--time"], None),
+            Ok(InputSource::File(PathBuf::from("big.txt")))
+        );
+        assert_eq!(
+            parse(&["01", "--example", "2"], None),
+            Ok(InputSource::Example(2))
+        );
+    }
+
+    #[test]
+    fn flags_override_env_input() {
+        let parse =
+            |a: &[&str], env: Option<&str>| InputSource::parse(&args(a), env.map(Into::into));
+
+        assert_eq!(
+            parse(&["01"], Some("other.txt")),
+            Ok(InputSource::File(PathBuf::from("other.txt")))
+        );
+        assert_eq!(parse(&["01"], Some("-")), Ok(InputSource::Stdin));
+        assert_eq!(
+            parse(&["01", "--example", "1"], Some("other.txt")),
+            Ok(InputSource::Example(1))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_input_flags() {
+        assert!(InputSource::parse(&args(&["01", "--input"]), None).is_err());
+        assert!(InputSource::parse(&args(&["01", "--example", "x"]), None).is_err());
+        assert!(
+            InputSource::parse(&args(&["01", "--input", "a", "--example", "1"]), None).is_err()
+        );
+    }
+
+    #[test]
+    fn catches_panics() {
+        let result = call_guarded(|x: u32| -> u32 { panic!("bad input {x}") }, 3);
+        let Err(Failure::Panicked(message)) = result else {
+            panic!("expected a panic, got {result:?}");
+        };
+        assert!(message.starts_with("src/template/runner.rs:"));
+        assert!(message.ends_with(": bad input 3"));
+
+        assert_eq!(call_guarded(|x: u32| x + 1, 1), Ok(2));
+    }
+
+    #[test]
+    fn times_out_runaway_steps() {
+        let timeout = Duration::from_millis(10);
+        let slow = |(): &()| thread::sleep(Duration::from_secs(5));
+
+        let (result, elapsed, _) = run_isolated(slow, &Arc::new(()), Some(timeout));
+        assert_eq!(result, Err(Failure::TimedOut(timeout)));
+        assert_eq!(elapsed, timeout);
+
+        let input = Arc::new(21);
+        let (result, _, _) = run_isolated(|x: &u32| x * 2, &input, Some(Duration::from_secs(5)));
+        assert_eq!(result, Ok(42));
+        let (result, _, _) = run_isolated(|x: &u32| x * 2, &input, None);
+        assert_eq!(result, Ok(42));
+    }
+
+    #[test]
+    fn guards_bench_iterations() {
+        let config = BenchConfig {
+            warmup: Some(0),
+            reject_outliers: false,
+        };
+        let base_time = Duration::from_millis(200);
+
+        // panics on the third call, after the isolated first run would have passed.
+        let flaky = |calls: &AtomicU32| {
+            assert!(calls.fetch_add(1, Ordering::Relaxed) != 2, "third call");
+        };
+        let result = bench(
+            flaky,
+            &Arc::new(AtomicU32::new(1)),
+            &base_time,
+            &config,
+            false,
+            None,
+        );
+        assert!(
+            matches!(result, Err(Failure::Panicked(message)) if message.ends_with("third call"))
+        );
+
+        let timeout = Duration::from_millis(50);
+        let hangs = |calls: &AtomicU32| {
+            if calls.fetch_add(1, Ordering::Relaxed) == 2 {
+                thread::sleep(Duration::from_secs(5));
+            }
+        };
+        let input = Arc::new(AtomicU32::new(1));
+        let result = bench(hangs, &input, &base_time, &config, false, Some(timeout));
+        assert_eq!(result, Err(Failure::TimedOut(timeout)));
+
+        let report = bench(
+            |_: &u32| (),
+            &Arc::new(0),
+            &base_time,
+            &config,
+            false,
+            Some(timeout),
+        );
+        assert_eq!(report.map(|report| report.samples), Ok(10));
+    }
+
+    #[test]
+    fn reports_panicking_parts() {
+        let puzzle = PuzzleId::new(Year::__new_unchecked(2023), day!(8));
+        let part = |input: &str| -> Option<u32> { unreachable!("no path in {input}") };
+
+        let record = solve_part(part, &Arc::from("AAA"), puzzle, 2, OutputFormat::Json);
+        assert!(!record.success);
+        assert_eq!(record.answer, None);
+        assert!(record
+            .error
+            .unwrap()
+            .ends_with("internal error: entered unreachable code: no path in AAA"));
+    }
 }
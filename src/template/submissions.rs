@@ -0,0 +1,326 @@
+/// Local ledger of submitted answers, stored in `data/<year>/answers/<day>.toml`.
+/// Used to refuse submissions that are known to be wrong before they reach the server.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::aoc_client::Verdict;
+use crate::template::get_data_path;
+use crate::PuzzleId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    Wait,
+    WrongLevel,
+    Unknown,
+}
+
+impl From<&Verdict> for Outcome {
+    fn from(verdict: &Verdict) -> Self {
+        match verdict {
+            Verdict::Correct => Outcome::Correct,
+            Verdict::TooHigh(_) => Outcome::TooHigh,
+            Verdict::TooLow(_) => Outcome::TooLow,
+            Verdict::Incorrect(_) => Outcome::Incorrect,
+            Verdict::Wait(_) => Outcome::Wait,
+            Verdict::WrongLevel => Outcome::WrongLevel,
+            Verdict::Unknown(_) => Outcome::Unknown,
+        }
+    }
+}
+
+impl Outcome {
+    fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Outcome::TooHigh | Outcome::TooLow | Outcome::Incorrect
+        )
+    }
+}
+
+/// A single submitted answer and the server's verdict.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Unix timestamp in seconds.
+    pub submitted_at: u64,
+}
+
+/// Reasons for refusing to submit an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    KnownWrong(Outcome),
+    /// The answer is at or above an answer that was too high.
+    AboveBound(String),
+    /// The answer is at or below an answer that was too low.
+    BelowBound(String),
+    Cooldown(Duration),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with answer {answer}.")
+            }
+            Refusal::KnownWrong(Outcome::TooHigh) => {
+                write!(f, "this answer was submitted before and was too high.")
+            }
+            Refusal::KnownWrong(Outcome::TooLow) => {
+                write!(f, "this answer was submitted before and was too low.")
+            }
+            Refusal::KnownWrong(_) => {
+                write!(f, "this answer was submitted before and was wrong.")
+            }
+            Refusal::AboveBound(bound) => {
+                write!(
+                    f,
+                    "the answer must be lower than {bound}, which was too high."
+                )
+            }
+            Refusal::BelowBound(bound) => {
+                write!(
+                    f,
+                    "the answer must be higher than {bound}, which was too low."
+                )
+            }
+            Refusal::Cooldown(duration) => {
+                write!(
+                    f,
+                    "an answer was submitted too recently, wait {duration:?}."
+                )
+            }
+        }
+    }
+}
+
+/// Known-good answers of a day, used by `cargo check-answers`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part_1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part_2: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ledger {
+    /// Unix timestamp in seconds until which the server asked us not to submit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cooldown_until: Option<u64>,
+    /// Filled in by correct submissions, can also be edited by hand.
+    #[serde(default)]
+    pub answers: Answers,
+    #[serde(default, rename = "submission")]
+    pub submissions: Vec<Submission>,
+}
+
+#[must_use]
+pub fn get_path(puzzle: PuzzleId) -> PathBuf {
+    get_data_path("answers", puzzle, "toml")
+}
+
+#[must_use]
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+impl Ledger {
+    /// Loads the ledger for a puzzle, returns an empty ledger if none exists yet.
+    pub fn load(puzzle: PuzzleId) -> io::Result<Self> {
+        match fs::read_to_string(get_path(puzzle)) {
+            Ok(s) => toml::from_str(&s).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, puzzle: PuzzleId) -> io::Result<()> {
+        let path = get_path(puzzle);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let s = toml::to_string(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, s)
+    }
+
+    /// Returns the known-good answer for a part, if any.
+    #[must_use]
+    pub fn correct_answer(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.answers.part_1.as_deref(),
+            2 => self.answers.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Checks whether an answer may be submitted at time `now`.
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        if let Some(correct) = self.correct_answer(part) {
+            return Err(Refusal::AlreadySolved(correct.to_string()));
+        }
+
+        let submissions = self.submissions.iter().filter(|s| s.part == part);
+
+        if let Some(previous) = submissions
+            .clone()
+            .find(|s| s.answer == answer && s.outcome.is_wrong())
+        {
+            return Err(Refusal::KnownWrong(previous.outcome));
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            for s in submissions {
+                let Ok(bound) = s.answer.parse::<i128>() else {
+                    continue;
+                };
+
+                match s.outcome {
+                    Outcome::TooHigh if value >= bound => {
+                        return Err(Refusal::AboveBound(s.answer.clone()))
+                    }
+                    Outcome::TooLow if value <= bound => {
+                        return Err(Refusal::BelowBound(s.answer.clone()))
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        match self.cooldown_until {
+            Some(until) if until > now => Err(Refusal::Cooldown(Duration::from_secs(until - now))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Records a submitted answer and the server's verdict.
+    /// Wrong answers and submissions that came too early start a cooldown, see [`Verdict::wait`].
+    pub fn record(&mut self, part: u8, answer: &str, verdict: &Verdict, now: u64) {
+        if let Some(wait) = verdict.wait() {
+            self.cooldown_until = Some(now + wait.as_secs());
+        }
+
+        match (verdict, part) {
+            (Verdict::Correct, 1) => self.answers.part_1 = Some(answer.to_string()),
+            (Verdict::Correct, 2) => self.answers.part_2 = Some(answer.to_string()),
+            _ => {}
+        }
+
+        self.submissions.push(Submission {
+            part,
+            answer: answer.to_string(),
+            outcome: verdict.into(),
+            submitted_at: now,
+        });
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Ledger, Outcome, Refusal};
+    use crate::template::aoc_client::Verdict;
+    use std::time::Duration;
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let mut ledger = Ledger::default();
+        ledger.record(1, "abc", &Verdict::Incorrect(None), 0);
+
+        assert_eq!(
+            ledger.check(1, "abc", 100),
+            Err(Refusal::KnownWrong(Outcome::Incorrect))
+        );
+        assert_eq!(ledger.check(1, "abd", 100), Ok(()));
+        assert_eq!(ledger.check(2, "abc", 100), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let mut ledger = Ledger::default();
+        ledger.record(1, "100", &Verdict::TooHigh(None), 0);
+        ledger.record(1, "10", &Verdict::TooLow(None), 0);
+
+        assert_eq!(
+            ledger.check(1, "150", 100),
+            Err(Refusal::AboveBound("100".into()))
+        );
+        assert_eq!(
+            ledger.check(1, "10", 100),
+            Err(Refusal::KnownWrong(Outcome::TooLow))
+        );
+        assert_eq!(
+            ledger.check(1, "-5", 100),
+            Err(Refusal::BelowBound("10".into()))
+        );
+        assert_eq!(ledger.check(1, "50", 100), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let mut ledger = Ledger::default();
+        ledger.record(2, "42", &Verdict::Correct, 0);
+
+        assert_eq!(
+            ledger.check(2, "43", 100),
+            Err(Refusal::AlreadySolved("42".into()))
+        );
+        assert_eq!(ledger.correct_answer(2), Some("42"));
+        assert_eq!(ledger.correct_answer(1), None);
+    }
+
+    #[test]
+    fn respects_cooldown() {
+        let mut ledger = Ledger::default();
+        ledger.record(1, "1", &Verdict::Wait(Duration::from_secs(60)), 1000);
+
+        assert_eq!(
+            ledger.check(1, "1", 1030),
+            Err(Refusal::Cooldown(Duration::from_secs(30)))
+        );
+        assert_eq!(ledger.check(1, "1", 1060), Ok(()));
+    }
+
+    #[test]
+    fn wrong_answers_start_cooldown() {
+        let mut ledger = Ledger::default();
+        let wait = Some(Duration::from_secs(60));
+        ledger.record(1, "100", &Verdict::TooHigh(wait), 1000);
+
+        assert_eq!(
+            ledger.check(1, "50", 1010),
+            Err(Refusal::Cooldown(Duration::from_secs(50)))
+        );
+        assert_eq!(ledger.check(1, "50", 1060), Ok(()));
+    }
+
+    #[test]
+    fn toml_round_trip() {
+        let mut ledger = Ledger::default();
+        ledger.record(1, "100", &Verdict::TooHigh(None), 10);
+        ledger.record(1, "1", &Verdict::Wait(Duration::from_secs(5)), 20);
+
+        ledger.record(2, "7", &Verdict::Correct, 30);
+
+        let s = toml::to_string(&ledger).unwrap();
+        assert!(s.contains("cooldown_until = 25"));
+        assert!(s.contains("[answers]\npart_2 = \"7\""));
+        assert!(s.contains("[[submission]]"));
+        assert!(s.contains(r#"outcome = "too_high""#));
+        assert_eq!(toml::from_str::<Ledger>(&s).unwrap(), ledger);
+    }
+}
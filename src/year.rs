@@ -0,0 +1,179 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A year in which advent of code took place (i.e. an integer of 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < 2015 {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Reads the year from the `AOC_YEAR` environment variable.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    // Not part of the public API
+    // Reads the year from the directory of a solution file, e.g. `src/bin/2023/01.rs`.
+    // Evaluated in a const context by `solution!`, so a misplaced file fails to compile.
+    #[doc(hidden)]
+    pub const fn __from_path(path: &str) -> Self {
+        const MESSAGE: &str =
+            "solution files must be placed in a year directory, e.g. `src/bin/2023/01.rs`";
+
+        let bytes = path.as_bytes();
+
+        let mut end = bytes.len();
+        while end > 0 && !is_separator(bytes[end - 1]) {
+            end -= 1;
+        }
+
+        assert!(end >= 5, "{}", MESSAGE);
+        let start = end - 5;
+        assert!(start == 0 || is_separator(bytes[start - 1]), "{}", MESSAGE);
+
+        let mut year = 0;
+        let mut i = start;
+        while i < end - 1 {
+            assert!(bytes[i].is_ascii_digit(), "{}", MESSAGE);
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        assert!(year >= 2015, "{}", MESSAGE);
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the number of days in this year's advent, starting with 2025 there are 12.
+    pub fn day_count(self) -> u8 {
+        if self.0 >= 2025 {
+            12
+        } else {
+            25
+        }
+    }
+}
+
+const fn is_separator(byte: u8) -> bool {
+    byte == b'/' || byte == b'\\'
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl PartialEq<u16> for Year {
+    fn eq(&self, other: &u16) -> bool {
+        self.0.eq(other)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Serialize for Year {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Year {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let year = u16::deserialize(deserializer)?;
+        Self::new(year).ok_or_else(|| de::Error::custom(YearFromStrError))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().ok(), Some(Year(2023)));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("23".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn reads_year_from_path() {
+        assert_eq!(Year::__from_path("src/bin/2023/01.rs"), Year(2023));
+        assert_eq!(
+            Year::__from_path("/home/me/aoc/src/bin/2015/25.rs"),
+            Year(2015)
+        );
+        assert_eq!(
+            Year::__from_path("C:\\aoc\\src\\bin\\2016\\07.rs"),
+            Year(2016)
+        );
+        assert_eq!(Year::__from_path("2017/01.rs"), Year(2017));
+    }
+
+    #[test]
+    #[should_panic(expected = "year directory")]
+    fn rejects_paths_without_year() {
+        let _ = Year::__from_path("src/bin/01.rs");
+    }
+
+    #[test]
+    fn counts_days() {
+        assert_eq!(Year(2023).day_count(), 25);
+        assert_eq!(Year(2025).day_count(), 12);
+    }
+}
@@ -0,0 +1,36 @@
+advent_of_code::solution!({{day}});
+
+use advent_of_code::grid::Grid;
+
+// {{year}} day {{day}}: {{title}}
+
+fn parse(input: &str) -> Grid<char> {
+    Grid::parse(input, |c| c).unwrap()
+}
+
+pub fn part_one(input: &str) -> Option<{{return_type}}> {
+    let grid = parse(input);
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{return_type}}> {
+    let grid = parse(input);
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+}
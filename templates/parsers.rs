@@ -0,0 +1,39 @@
+use advent_of_code::parsers::space_separated_numbers_parser;
+
+advent_of_code::solution!({{day}});
+
+// {{year}} day {{day}}: {{title}}
+
+fn parse(input: &str) -> Vec<Vec<{{return_type}}>> {
+    input
+        .lines()
+        .map(|line| space_separated_numbers_parser(line).unwrap().1)
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Option<{{return_type}}> {
+    let numbers = parse(input);
+    None
+}
+
+pub fn part_two(input: &str) -> Option<{{return_type}}> {
+    let numbers = parse(input);
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, None);
+    }
+}